@@ -1,14 +1,166 @@
 use crate::*;
+use near_sdk::{ext_contract, log, Gas, PromiseResult};
 
-/// CUSTOM - owner can burn a locked token for a given user, reducing the enumerable->nft_supply_for_type
+const GAS_FOR_RESOLVE_BURN: Gas = 10_000_000_000_000;
+const GAS_FOR_NFT_BURN_CALL: Gas = 25_000_000_000_000 + GAS_FOR_RESOLVE_BURN;
+const NO_DEPOSIT: Balance = 0;
+
+#[ext_contract(ext_non_fungible_token_burn_receiver)]
+trait NonFungibleTokenBurnReceiver {
+    fn nft_on_burn(
+        &mut self,
+        owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    );
+}
+
+#[ext_contract(ext_burn_resolver)]
+trait NonFungibleTokenBurnResolver {
+    fn nft_resolve_burn(
+        &mut self,
+        token_id: TokenId,
+        token: Token,
+        metadata: TokenMetadata,
+        storage_released: U64,
+    ) -> bool;
+}
+
+/// CUSTOM - token owner can burn their token, owner can burn a locked token for a given user
 #[near_bindgen]
 impl Contract {
     #[payable]
     pub fn nft_burn(
         &mut self,
-        token_id: Option<TokenId>,
+        token_id: TokenId,
     ) {
-        assert_eq!(self.is_token_locked(token_id), true, "Token must be locked");
-        //TODO burn token
+        assert_one_yocto();
+        let owner_id = self.tokens_by_id.get(&token_id).expect("Token not found").owner_id;
+        let predecessor_account_id = env::predecessor_account_id();
+        if predecessor_account_id != owner_id {
+            assert_eq!(predecessor_account_id, self.owner_id, "Unauthorized");
+            assert!(self.is_token_locked(token_id.clone()), "Token must be locked");
+        }
+
+        let initial_storage_usage = env::storage_usage();
+        let (token, _) = self.internal_burn(&token_id);
+        emit_nft_burn(&token.owner_id, vec![token_id]);
+
+        if self.use_storage_fees {
+            self.internal_refund_burned_storage(token.owner_id, initial_storage_usage - env::storage_usage());
+        }
+    }
+
+    pub fn claim_burn_refund(&mut self) -> U128 {
+        let predecessor_account_id = env::predecessor_account_id();
+        let (amount, claimable_at) = self.pending_burn_refunds.get(&predecessor_account_id).expect("No pending burn refund");
+        assert!(env::block_timestamp() >= claimable_at, "Burn refund is not claimable yet");
+        self.pending_burn_refunds.remove(&predecessor_account_id);
+        Promise::new(predecessor_account_id).transfer(amount);
+        U128(amount)
+    }
+
+    /// burns every token or none, all of them must be owned by the caller
+    #[payable]
+    pub fn nft_burn_batch(
+        &mut self,
+        token_ids: Vec<TokenId>,
+    ) {
+        assert_one_yocto();
+        assert!(!token_ids.is_empty(), "No token ids given");
+        let predecessor_account_id = env::predecessor_account_id();
+
+        let initial_storage_usage = env::storage_usage();
+        for token_id in &token_ids {
+            let (token, _) = self.internal_burn(token_id);
+            assert_eq!(token.owner_id, predecessor_account_id, "Predecessor must be the token owner.");
+        }
+        emit_nft_burn(&predecessor_account_id, token_ids);
+
+        if self.use_storage_fees {
+            self.internal_refund_burned_storage(predecessor_account_id, initial_storage_usage - env::storage_usage());
+        }
+    }
+
+    /// burns the token and calls `nft_on_burn` on the receiver, the token is restored if that call fails
+    /// so nft_burn is only logged by nft_resolve_burn once the call succeeded
+    #[payable]
+    pub fn nft_burn_call(
+        &mut self,
+        token_id: TokenId,
+        receiver_id: ValidAccountId,
+        msg: String,
+    ) -> Promise {
+        assert_one_yocto();
+        let owner_id = self.tokens_by_id.get(&token_id).expect("Token not found").owner_id;
+        assert_eq!(
+            env::predecessor_account_id(),
+            owner_id,
+            "Predecessor must be the token owner."
+        );
+
+        let initial_storage_usage = env::storage_usage();
+        let (token, metadata) = self.internal_remove_token(&token_id);
+        let storage_released = initial_storage_usage - env::storage_usage();
+
+        ext_non_fungible_token_burn_receiver::nft_on_burn(
+            token.owner_id.clone(),
+            token_id.clone(),
+            msg,
+            receiver_id.as_ref(),
+            NO_DEPOSIT,
+            env::prepaid_gas() - GAS_FOR_NFT_BURN_CALL,
+        )
+            .then(ext_burn_resolver::nft_resolve_burn(
+                token_id,
+                token,
+                metadata,
+                U64(storage_released),
+                &env::current_account_id(),
+                NO_DEPOSIT,
+                GAS_FOR_RESOLVE_BURN,
+            ))
+    }
+
+    /// Returns `true` if the token stayed burned, `false` if it was restored to its owner.
+    #[private]
+    pub fn nft_resolve_burn(
+        &mut self,
+        token_id: TokenId,
+        token: Token,
+        metadata: TokenMetadata,
+        storage_released: U64,
+    ) -> bool {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            let mut storage_released = u64::from(storage_released);
+            let initial_storage_usage = env::storage_usage();
+            // a token minted again under the same id owns the extras now
+            if !self.tokens_by_id.contains_key(&token_id) {
+                self.internal_clear_token_extras(&token_id);
+            }
+            self.internal_release_unique_media(&token, &metadata);
+            storage_released += initial_storage_usage - env::storage_usage();
+            emit_nft_burn(&token.owner_id, vec![token_id]);
+            if self.use_storage_fees {
+                self.internal_refund_burned_storage(token.owner_id, storage_released);
+            }
+            return true;
+        }
+
+        if self.tokens_by_id.contains_key(&token_id) {
+            // The token id was minted again in the meantime. Can't restore it.
+            return true;
+        }
+
+        log!("Restore burned {} to @{}", token_id, token.owner_id);
+
+        if let Some(token_type) = &token.token_type {
+            self.internal_add_token_to_type(token_type, &token_id);
+        }
+        self.internal_add_token_to_owner(&token.owner_id, &token_id);
+        self.token_metadata_by_id.insert(&token_id, &metadata);
+        self.tokens_by_id.insert(&token_id, &token);
+
+        false
     }
-}
\ No newline at end of file
+}
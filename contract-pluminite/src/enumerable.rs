@@ -1,5 +1,8 @@
 use crate::*;
 
+/// Enumerations return an empty page when from_index is past the end instead of panicking.
+/// from_index and limit count raw entries, hidden tokens (and non-matches of filtering views) are dropped
+/// from the page afterwards, so a page can be short and clients should advance from_index by limit.
 #[near_bindgen]
 impl Contract {
 
@@ -9,14 +12,36 @@ impl Contract {
         limit: Option<u64>
     ) -> Vec<JsonToken> {
         let keys = self.token_metadata_by_id.keys_as_vector();
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-        keys.iter()
-           .skip(start as usize)
-           .take(limit.unwrap_or(0) as usize)
-           .map(|token_id| self.nft_token(token_id.clone()).unwrap())
+        let start = u128::from(from_index.unwrap_or(U128(0))) as u64;
+        let end = min(start.saturating_add(limit.unwrap_or(0).min(MAX_PAGE_SIZE)), keys.len());
+        (start..end)
+           .map(|i| keys.get(i).unwrap())
+           .filter(|token_id| !self.hidden_tokens.contains(token_id))
+           .map(|token_id| self.nft_token(token_id).unwrap())
            .collect()
     }
 
+    /// returns the from_index of the next page, None once every token has been returned
+    pub fn nft_tokens_paged(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> (Vec<JsonToken>, Option<U128>) {
+        let keys = self.token_metadata_by_id.keys_as_vector();
+        let limit = limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE) as usize;
+        let mut index = u128::from(from_index.unwrap_or(U128(0))) as u64;
+        let mut tokens = vec![];
+        while index < keys.len() && tokens.len() < limit {
+            let token_id = keys.get(index).unwrap();
+            if !self.hidden_tokens.contains(&token_id) {
+                tokens.push(self.nft_token(token_id).unwrap());
+            }
+            index += 1;
+        }
+        let next_index = if index < keys.len() { Some(U128(index as u128)) } else { None };
+        (tokens, next_index)
+    }
+
     pub fn nft_tokens_from_end(
         &self,
         from_index: Option<U128>,
@@ -26,9 +51,11 @@ impl Contract {
         let keys = self.token_metadata_by_id.keys_as_vector();
         let total_keys = keys.len() as u128;
         let from_index_prepared = u128::from(from_index.unwrap_or(U128(0)));
-        let mut limit_prepared = u128::from(limit.unwrap_or(total_keys as u128));
+        let mut limit_prepared = limit.unwrap_or(total_keys);
 
-        assert!(total_keys > from_index_prepared, "Illegal from_index");
+        if from_index_prepared >= total_keys {
+            return tmp;
+        }
 
         if total_keys - from_index_prepared < limit_prepared{
             limit_prepared = total_keys - from_index_prepared;
@@ -38,25 +65,43 @@ impl Contract {
         let end: u128 = start + limit_prepared;
 
         for i in (start..end).rev() {
-            tmp.push(self.nft_token(keys.get(u128::from(i)).unwrap()).unwrap());
+            let token_id = keys.get(i as u64).unwrap();
+            if !self.hidden_tokens.contains(&token_id) {
+                tmp.push(self.nft_token(token_id).unwrap());
+            }
         }
         tmp
     }
 
+    /// newest first, burns move the last minted token into the burned slot so the order is approximate after burns
+    pub fn nft_latest_tokens(
+        &self,
+        count: u64,
+    ) -> Vec<JsonToken> {
+        let keys = self.token_metadata_by_id.keys_as_vector();
+        (0..keys.len())
+            .rev()
+            .map(|i| keys.get(i).unwrap())
+            .filter(|token_id| !self.hidden_tokens.contains(token_id))
+            .take(count.min(MAX_PAGE_SIZE) as usize)
+            .map(|token_id| self.nft_token(token_id).unwrap())
+            .collect()
+    }
+
     pub fn nft_tokens_batch(
         &self,
         token_ids: Vec<String>,
     ) -> Vec<JsonToken> {
         let mut tmp = vec![];
-        for i in 0..token_ids.len() {
-            tmp.push(self.nft_token(token_ids[i].clone()).unwrap());
+        for token_id in token_ids {
+            tmp.push(self.nft_token(token_id).unwrap());
         }
         tmp
     }
 
     pub fn nft_supply_for_type(
         &self,
-        token_type: &String,
+        token_type: String,
     ) -> U64 {
         let tokens_per_type = self.tokens_per_type.get(&token_type);
         if let Some(tokens_per_type) = tokens_per_type {
@@ -66,6 +111,17 @@ impl Contract {
         }
     }
 
+    /// unknown token types count as zero
+    pub fn nft_supply_for_types_total(
+        &self,
+        token_types: Vec<String>,
+    ) -> U64 {
+        U64(token_types
+            .into_iter()
+            .map(|token_type| u64::from(self.nft_supply_for_type(token_type)))
+            .sum())
+    }
+
     pub fn nft_tokens_for_type(
         &self,
         token_type: String,
@@ -81,13 +137,110 @@ impl Contract {
         };
         let keys = tokens.as_vector();
         let start = u64::from(from_index);
-        let end = min(start + limit, keys.len());
+        let end = min(start.saturating_add(limit), keys.len());
         for i in start..end {
-            tmp.push(self.nft_token(keys.get(i).unwrap()).unwrap());
+            let token_id = keys.get(i).unwrap();
+            if !self.hidden_tokens.contains(&token_id) {
+                tmp.push(self.nft_token(token_id).unwrap());
+            }
         }
         tmp
     }
 
+    /// raw ids for snapshots, hidden tokens included like nft_token_ids_for_creator
+    pub fn nft_token_ids_for_type(
+        &self,
+        token_type: String,
+        from_index: U64,
+        limit: U64,
+    ) -> Vec<TokenId> {
+        let tokens_per_type = self.tokens_per_type.get(&token_type);
+        let tokens = if let Some(tokens_per_type) = tokens_per_type {
+            tokens_per_type
+        } else {
+            return vec![];
+        };
+        let keys = tokens.as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(u64::from(limit)), keys.len());
+        (start..end).map(|i| keys.get(i).unwrap()).collect()
+    }
+
+    /// best-effort case-insensitive title search, scans at most MAX_PAGE_SIZE tokens from from_index
+    pub fn nft_search_by_title(
+        &self,
+        prefix: String,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<JsonToken> {
+        let prefix = prefix.to_lowercase();
+        let keys = self.token_metadata_by_id.keys_as_vector();
+        let values = self.token_metadata_by_id.values_as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(MAX_PAGE_SIZE), keys.len());
+        (start..end)
+            .filter(|i| matches!(values.get(*i).unwrap().title, Some(title) if title.to_lowercase().starts_with(&prefix)))
+            .map(|i| keys.get(i).unwrap())
+            .filter(|token_id| !self.hidden_tokens.contains(token_id))
+            .take(limit as usize)
+            .map(|token_id| self.nft_token(token_id).unwrap())
+            .collect()
+    }
+
+    /// from_index and limit page over all tokens, so a page can return fewer than limit matches
+    pub fn nft_untyped_tokens(
+        &self,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<JsonToken> {
+        let keys = self.token_metadata_by_id.keys_as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        (start..end)
+            .map(|i| keys.get(i).unwrap())
+            .filter(|token_id| !self.hidden_tokens.contains(token_id))
+            .filter(|token_id| self.tokens_by_id.get(token_id).unwrap().token_type.is_none())
+            .map(|token_id| self.nft_token(token_id).unwrap())
+            .collect()
+    }
+
+    /// royalties account_id would receive if every token of the page sold at assumed_price
+    pub fn estimate_royalties_for(
+        &self,
+        account_id: AccountId,
+        assumed_price: U128,
+        from_index: U64,
+        limit: u64,
+    ) -> U128 {
+        let keys = self.token_metadata_by_id.keys_as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        U128((start..end)
+            .filter_map(|i| self.tokens_by_id.get(&keys.get(i).unwrap()).unwrap().royalty.get(&account_id).copied())
+            .map(|royalty| u128::from(royalty_to_payout(royalty, assumed_price.into())))
+            .sum())
+    }
+
+    /// from_index and limit page over all tokens, so a page can return fewer than limit matches
+    pub fn nft_tokens_with_royalty_for(
+        &self,
+        account_id: AccountId,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<JsonToken> {
+        let keys = self.token_metadata_by_id.keys_as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        (start..end)
+            .map(|i| keys.get(i).unwrap())
+            .filter(|token_id| !self.hidden_tokens.contains(token_id))
+            .filter(|token_id| {
+                self.tokens_by_id.get(token_id).unwrap().royalty.contains_key(&account_id)
+            })
+            .map(|token_id| self.nft_token(token_id).unwrap())
+            .collect()
+    }
+
     pub fn nft_supply_for_owner(
         &self,
         account_id: AccountId,
@@ -100,6 +253,67 @@ impl Contract {
         }
     }
 
+    /// per type counts within a page of the owner's tokens, sorted by type, untyped tokens are not counted
+    pub fn owner_type_breakdown(
+        &self,
+        account_id: AccountId,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<(String, U64)> {
+        let tokens = if let Some(tokens_owner) = self.tokens_per_owner.get(&account_id) {
+            tokens_owner
+        } else {
+            return vec![];
+        };
+        let keys = tokens.as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        let mut counts: HashMap<TokenType, u64> = HashMap::new();
+        for i in start..end {
+            if let Some(token_type) = self.tokens_by_id.get(&keys.get(i).unwrap()).unwrap().token_type {
+                *counts.entry(token_type).or_insert(0) += 1;
+            }
+        }
+        let mut breakdown: Vec<(String, U64)> = counts.into_iter().map(|(token_type, count)| (token_type, U64(count))).collect();
+        breakdown.sort_by(|a, b| a.0.cmp(&b.0));
+        breakdown
+    }
+
+    /// owned tokens with at least one approval, scans every token of the owner
+    pub fn nft_approved_count(
+        &self,
+        account_id: AccountId,
+    ) -> U64 {
+        let tokens_owner = self.tokens_per_owner.get(&account_id);
+        if let Some(tokens_owner) = tokens_owner {
+            U64(tokens_owner
+                .iter()
+                .filter(|token_id| !self.tokens_by_id.get(token_id).unwrap().approved_account_ids.is_empty())
+                .count() as u64)
+        } else {
+            U64(0)
+        }
+    }
+
+    /// holders with 1, 2-5 and 6+ tokens as (bucket start, count), sum the pages for the full histogram
+    pub fn holder_distribution(
+        &self,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<(u64, u64)> {
+        let keys = self.holders.as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        let mut buckets = vec![(1, 0), (2, 0), (6, 0)];
+        for i in start..end {
+            let held = self.tokens_per_owner.get(&keys.get(i).unwrap()).map_or(0, |tokens| tokens.len());
+            if let Some(bucket) = buckets.iter_mut().rev().find(|(bucket_start, _)| held >= *bucket_start) {
+                bucket.1 += 1;
+            }
+        }
+        buckets
+    }
+
     pub fn nft_supply_for_creator(
         &self,
         account_id: AccountId,
@@ -112,11 +326,43 @@ impl Contract {
         }
     }
 
+    /// (created, owned) in one call
+    pub fn account_summary(
+        &self,
+        account_id: AccountId,
+    ) -> (U64, U128) {
+        let created = u128::from(self.nft_supply_for_creator(account_id.clone())) as u64;
+        (U64(created), self.nft_supply_for_owner(account_id))
+    }
+
     pub fn nft_tokens_for_owner(
         &self,
         account_id: AccountId,
         from_index: Option<U128>,
         limit: Option<u64>,
+    ) -> Vec<JsonToken> {
+        let tokens_owner = self.tokens_per_owner.get(&account_id);
+        let tokens = if let Some(tokens_owner) = tokens_owner {
+            tokens_owner
+        } else {
+            return vec![];
+        };
+        let keys = tokens.as_vector();
+        let start = u128::from(from_index.unwrap_or(U128(0))) as u64;
+        let end = min(start.saturating_add(limit.unwrap_or(0)), keys.len());
+        (start..end)
+           .map(|i| keys.get(i).unwrap())
+           .filter(|token_id| !self.hidden_tokens.contains(token_id))
+           .map(|token_id| self.nft_token(token_id).unwrap())
+           .collect()
+    }
+
+    /// newest first - follows insertion order into the owner's set (removals swap the last token in)
+    pub fn nft_tokens_for_owner_reversed(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
     ) -> Vec<JsonToken> {
         let tokens_owner = self.tokens_per_owner.get(&account_id);
         let tokens = if let Some(tokens_owner) = tokens_owner {
@@ -126,13 +372,32 @@ impl Contract {
         };
         let keys = tokens.as_vector();
         let start = u128::from(from_index.unwrap_or(U128(0)));
-        keys.iter()
+        (0..keys.len())
+           .rev()
            .skip(start as usize)
            .take(limit.unwrap_or(0) as usize)
-           .map(|token_id| self.nft_token(token_id.clone()).unwrap())
+           .map(|i| keys.get(i).unwrap())
+           .filter(|token_id| !self.hidden_tokens.contains(token_id))
+           .map(|token_id| self.nft_token(token_id).unwrap())
            .collect()
     }
 
+    /// each token is paired with whether its type is locked
+    pub fn nft_tokens_for_owner_with_locks(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<(JsonToken, bool)> {
+        self.nft_tokens_for_owner(account_id, from_index, limit)
+            .into_iter()
+            .map(|token| {
+                let locked = self.internal_is_type_locked(&token.token_type);
+                (token, locked)
+            })
+            .collect()
+    }
+
     pub fn nft_tokens_for_creator(
         &self,
         account_id: AccountId,
@@ -148,10 +413,94 @@ impl Contract {
         };
         let keys = tokens.as_vector();
         let start = u64::from(from_index);
-        let end = min(start + u64::from(limit), keys.len());
+        let end = min(start.saturating_add(limit), keys.len());
         for i in start..end {
-            tmp.push(self.nft_token(keys.get(i).unwrap()).unwrap());
+            // burned tokens stay in the creator set, and their id may since be minted again by someone else,
+            // tokens without a recorded creator are listed
+            let token_id = keys.get(i).unwrap();
+            if self.hidden_tokens.contains(&token_id) {
+                continue;
+            }
+            if let Some(token) = self.nft_token(token_id) {
+                let created_by_account = match &token.creator_id {
+                    Some(creator_id) => creator_id == &account_id,
+                    None => true,
+                };
+                if created_by_account {
+                    tmp.push(token);
+                }
+            }
         }
         tmp
     }
+
+    /// raw ids of the creator set, burned ids included
+    pub fn nft_token_ids_for_creator(
+        &self,
+        account_id: AccountId,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<TokenId> {
+        let tokens = if let Some(tokens_creator) = self.tokens_per_creator.get(&account_id) {
+            tokens_creator
+        } else {
+            return vec![];
+        };
+        let keys = tokens.as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        (start..end).map(|i| keys.get(i).unwrap()).collect()
+    }
+
+    pub fn nft_tokens_for_creator_and_type(
+        &self,
+        account_id: AccountId,
+        token_type: String,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<JsonToken> {
+        let tokens_creator = self.tokens_per_creator.get(&account_id);
+        let tokens_per_type = self.tokens_per_type.get(&token_type);
+        let (tokens, tokens_per_type) = if let (Some(tokens_creator), Some(tokens_per_type)) = (tokens_creator, tokens_per_type) {
+            (tokens_creator, tokens_per_type)
+        } else {
+            return vec![];
+        };
+        let keys = tokens.as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        (start..end)
+            .map(|i| keys.get(i).unwrap())
+            .filter(|token_id| tokens_per_type.contains(token_id))
+            .filter(|token_id| self.internal_is_created_by(token_id, &account_id))
+            .filter(|token_id| !self.hidden_tokens.contains(token_id))
+            .map(|token_id| self.nft_token(token_id).unwrap())
+            .collect()
+    }
+
+    /// creator's tokens whose type is currently locked, burned and reminted ids are skipped
+    pub fn nft_tokens_for_creator_locked(
+        &self,
+        account_id: AccountId,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<JsonToken> {
+        let tokens = if let Some(tokens_creator) = self.tokens_per_creator.get(&account_id) {
+            tokens_creator
+        } else {
+            return vec![];
+        };
+        let keys = tokens.as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        (start..end)
+            .map(|i| keys.get(i).unwrap())
+            .filter(|token_id| self.internal_is_created_by(token_id, &account_id))
+            .filter(|token_id| !self.hidden_tokens.contains(token_id))
+            .filter(|token_id| {
+                matches!(self.tokens_by_id.get(token_id), Some(token) if self.internal_is_type_locked(&token.token_type))
+            })
+            .map(|token_id| self.nft_token(token_id).unwrap())
+            .collect()
+    }
 }
@@ -0,0 +1,49 @@
+use crate::*;
+use near_sdk::serde_json;
+
+pub const EVENT_STANDARD: &str = "nep171";
+pub const EVENT_VERSION: &str = "1.0.0";
+/// contract specific events stay out of nep171 so its indexers don't see unknown event names
+pub const PLUMINITE_EVENT_STANDARD: &str = "pluminite";
+pub const PLUMINITE_EVENT_VERSION: &str = "1.0.0";
+
+/// NEP-171 event log, one data entry per owner so batches list all their token ids together
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct NftEventLog<T: Serialize> {
+    standard: String,
+    version: String,
+    event: String,
+    data: Vec<T>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct NftBurnData {
+    owner_id: AccountId,
+    token_ids: Vec<TokenId>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct TokenTypesUnlockedData {
+    token_types: Vec<TokenType>,
+}
+
+fn emit_event<T: Serialize>(standard: &str, version: &str, event: &str, data: Vec<T>) {
+    let log = NftEventLog {
+        standard: standard.to_string(),
+        version: version.to_string(),
+        event: event.to_string(),
+        data,
+    };
+    env::log(format!("EVENT_JSON:{}", serde_json::to_string(&log).unwrap()).as_bytes());
+}
+
+pub(crate) fn emit_nft_burn(owner_id: &AccountId, token_ids: Vec<TokenId>) {
+    emit_event(EVENT_STANDARD, EVENT_VERSION, "nft_burn", vec![NftBurnData { owner_id: owner_id.clone(), token_ids }]);
+}
+
+pub(crate) fn emit_token_types_unlocked(token_types: Vec<TokenType>) {
+    emit_event(PLUMINITE_EVENT_STANDARD, PLUMINITE_EVENT_VERSION, "token_types_unlocked", vec![TokenTypesUnlockedData { token_types }]);
+}
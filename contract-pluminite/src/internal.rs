@@ -6,6 +6,24 @@ pub(crate) fn royalty_to_payout(a: u32, b: Balance) -> U128 {
     U128(a as u128 * b / 10_000u128)
 }
 
+pub(crate) fn is_relative_uri(uri: &str) -> bool {
+    !uri.is_empty() && !uri.contains("://") && !uri.starts_with("data:") && !is_ipfs_cid(uri)
+}
+
+/// the frontend stores media as a bare IPFS CID, optionally followed by a path, which is absolute
+pub(crate) fn is_ipfs_cid(uri: &str) -> bool {
+    let cid = uri.split('/').next().unwrap_or_default();
+    let is_cid_v0 = cid.len() == 46 && cid.starts_with("Qm") && cid.chars().all(|c| c.is_ascii_alphanumeric());
+    let is_cid_v1 = cid.len() >= 59
+        && cid.starts_with('b')
+        && cid.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    is_cid_v0 || is_cid_v1
+}
+
+pub(crate) fn join_uri(base_uri: &str, path: &str) -> String {
+    format!("{}/{}", base_uri.trim_end_matches('/'), path.trim_start_matches('/'))
+}
+
 pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     let mut hash = CryptoHash::default();
     hash.copy_from_slice(&env::sha256(account_id.as_bytes()));
@@ -43,21 +61,39 @@ pub(crate) fn refund_deposit(storage_used: u64) {
     }
 }
 
+pub(crate) fn refund_released_storage(account_id: AccountId, storage_released: u64) {
+    if storage_released > 0 {
+        Promise::new(account_id).transfer(Balance::from(storage_released) * env::storage_byte_cost());
+    }
+}
+
 // TODO: need a way for end users to determine how much an approval will cost.
 pub(crate) fn bytes_for_approved_account_id(account_id: &AccountId) -> u64 {
     // The extra 4 bytes are coming from Borsh serialization to store the length of the string.
     account_id.len() as u64 + 4 + size_of::<u64>() as u64
 }
 
+/// CUSTOM - an approval with an expiry also pays for its entry in approvals_expire_at
+pub(crate) fn bytes_for_approval_expiry(account_id: &AccountId) -> u64 {
+    account_id.len() as u64 + 4 + size_of::<u64>() as u64
+}
+
 pub(crate) fn refund_approved_account_ids_iter<'a, I>(
     account_id: AccountId,
     approved_account_ids: I,
+    approvals_expire_at: &HashMap<AccountId, u64>,
 ) -> Promise
 where
     I: Iterator<Item = &'a AccountId>,
 {
     let storage_released: u64 = approved_account_ids
-        .map(bytes_for_approved_account_id)
+        .map(|approved_account_id| {
+            if approvals_expire_at.contains_key(approved_account_id) {
+                bytes_for_approved_account_id(approved_account_id) + bytes_for_approval_expiry(approved_account_id)
+            } else {
+                bytes_for_approved_account_id(approved_account_id)
+            }
+        })
         .sum();
     Promise::new(account_id).transfer(Balance::from(storage_released) * env::storage_byte_cost())
 }
@@ -65,8 +101,9 @@ where
 pub(crate) fn refund_approved_account_ids(
     account_id: AccountId,
     approved_account_ids: &HashMap<AccountId, U64>,
+    approvals_expire_at: &HashMap<AccountId, u64>,
 ) -> Promise {
-    refund_approved_account_ids_iter(account_id, approved_account_ids.keys())
+    refund_approved_account_ids_iter(account_id, approved_account_ids.keys(), approvals_expire_at)
 }
 
 impl Contract {
@@ -78,6 +115,99 @@ impl Contract {
         );
     }
 
+    pub(crate) fn assert_type_manager_or_owner(&self, token_type: &TokenType) {
+        let predecessor_account_id = env::predecessor_account_id();
+        assert!(
+            predecessor_account_id == self.owner_id
+                || self.type_managers.get(token_type).as_ref() == Some(&predecessor_account_id),
+            "Owner's or type manager's method"
+        );
+    }
+
+    /// CUSTOM - untyped tokens are never locked
+    pub(crate) fn internal_is_type_locked(&self, token_type: &Option<TokenType>) -> bool {
+        if let Some(token_type) = token_type {
+            self.token_types_locked.contains(token_type)
+        } else {
+            false
+        }
+    }
+
+    /// CUSTOM - creator sets keep burned ids, which may since be minted again by someone else,
+    /// tokens without a recorded creator count as created by account_id
+    pub(crate) fn internal_is_created_by(&self, token_id: &TokenId, account_id: &AccountId) -> bool {
+        match self.creator_by_id.get(token_id) {
+            Some(creator_id) => &creator_id == account_id,
+            None => true,
+        }
+    }
+
+    pub(crate) fn internal_set_profile(&mut self, account_id: &AccountId, profile: &Profile) {
+        self.profiles.insert(account_id, profile);
+        self.profile_accounts.insert(account_id);
+    }
+
+    /// CUSTOM - a token type can override the global contract_royalty
+    pub(crate) fn internal_contract_royalty(&self, token_type: &Option<TokenType>) -> u32 {
+        token_type
+            .as_ref()
+            .and_then(|token_type| self.contract_royalty_by_type.get(token_type))
+            .unwrap_or(self.contract_royalty)
+    }
+
+    /// CUSTOM - gating checks of nft_mint, returns the first failing check
+    pub(crate) fn internal_check_mint(
+        &self,
+        token_type: Option<&TokenType>,
+        account_id: &AccountId,
+    ) -> Result<(), String> {
+        if self.require_profile_to_mint && self.profiles.get(account_id).is_none() {
+            return Err("Minter must have a profile".to_string());
+        }
+
+        self.internal_check_mint_type(token_type, Some(account_id))
+    }
+
+    /// CUSTOM - past the deadline but not finalized yet, the type keeps its unlimited cap until then
+    pub(crate) fn internal_is_open_edition_ended(&self, token_type: &TokenType) -> bool {
+        matches!(self.open_edition_until.get(token_type), Some(until) if env::block_timestamp() >= until)
+    }
+
+    /// the checks of internal_check_mint that don't depend on the minter's profile, without an
+    /// account_id a locked type fails like it does for everyone but the owner
+    pub(crate) fn internal_check_mint_type(
+        &self,
+        token_type: Option<&TokenType>,
+        account_id: Option<&AccountId>,
+    ) -> Result<(), String> {
+        if let Some(global_supply_cap) = self.global_supply_cap {
+            if u64::from(self.nft_total_supply()) >= global_supply_cap {
+                return Err("Cannot mint anymore tokens.".to_string());
+            }
+        }
+
+        if let Some(token_type) = token_type {
+            let cap = self
+                .supply_cap_by_type
+                .get(token_type)
+                .ok_or_else(|| "Token type must have supply cap.".to_string())?;
+            // only the owner can mint into a locked token type
+            if self.token_types_locked.contains(token_type) && account_id != Some(&self.owner_id) {
+                return Err("Only the owner can mint tokens of a locked type".to_string());
+            }
+            if self.internal_is_open_edition_ended(token_type) {
+                return Err("Open edition has ended".to_string());
+            }
+            if u64::from(*cap) != UNLIMITED_SUPPLY_CAP
+                && u64::from(self.nft_supply_for_type(token_type.clone())) >= u64::from(*cap)
+            {
+                return Err("Cannot mint anymore of token type.".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn internal_add_token_to_owner(
         &mut self,
         account_id: &AccountId,
@@ -86,7 +216,7 @@ impl Contract {
         let mut tokens_set = self.tokens_per_owner.get(account_id).unwrap_or_else(|| {
             UnorderedSet::new(
                 StorageKey::TokenPerOwnerInner {
-                    account_id_hash: hash_account_id(&account_id),
+                    account_id_hash: hash_account_id(account_id),
                 }
                 .try_to_vec()
                 .unwrap(),
@@ -94,6 +224,40 @@ impl Contract {
         });
         tokens_set.insert(token_id);
         self.tokens_per_owner.insert(account_id, &tokens_set);
+        self.holders.insert(account_id);
+    }
+
+    pub(crate) fn internal_add_token_to_type(
+        &mut self,
+        token_type: &TokenType,
+        token_id: &TokenId,
+    ) {
+        let mut tokens_per_type = self.tokens_per_type.get(token_type).unwrap_or_else(|| {
+            UnorderedSet::new(
+                StorageKey::TokensPerTypeInner {
+                    token_type_hash: hash_account_id(token_type),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        tokens_per_type.insert(token_id);
+        self.tokens_per_type.insert(token_type, &tokens_per_type);
+    }
+
+    pub(crate) fn internal_remove_token_from_type(
+        &mut self,
+        token_type: &TokenType,
+        token_id: &TokenId,
+    ) {
+        if let Some(mut tokens_per_type) = self.tokens_per_type.get(token_type) {
+            tokens_per_type.remove(token_id);
+            if tokens_per_type.is_empty() {
+                self.tokens_per_type.remove(token_type);
+            } else {
+                self.tokens_per_type.insert(token_type, &tokens_per_type);
+            }
+        }
     }
 
     pub(crate) fn internal_remove_token_from_owner(
@@ -108,11 +272,65 @@ impl Contract {
         tokens_set.remove(token_id);
         if tokens_set.is_empty() {
             self.tokens_per_owner.remove(account_id);
+            self.holders.remove(account_id);
         } else {
             self.tokens_per_owner.insert(account_id, &tokens_set);
         }
     }
 
+    /// CUSTOM - with a burn refund delay the refund is queued, each burn restarts the delay for the whole amount
+    pub(crate) fn internal_refund_burned_storage(&mut self, account_id: AccountId, storage_released: u64) {
+        match self.burn_refund_delay_ns {
+            Some(delay) if delay > 0 && storage_released > 0 => {
+                let (pending, _) = self.pending_burn_refunds.get(&account_id).unwrap_or((0, 0));
+                let amount = pending + Balance::from(storage_released) * env::storage_byte_cost();
+                self.pending_burn_refunds.insert(&account_id, &(amount, env::block_timestamp().saturating_add(delay)));
+            }
+            _ => refund_released_storage(account_id, storage_released),
+        }
+    }
+
+    /// removes the token from every index except the creator set, which keeps counting minted tokens
+    /// callers emit the nft_burn event so batches can log all ids at once
+    pub(crate) fn internal_burn(&mut self, token_id: &TokenId) -> (Token, TokenMetadata) {
+        let (token, metadata) = self.internal_remove_token(token_id);
+        self.internal_clear_token_extras(token_id);
+        self.internal_release_unique_media(&token, &metadata);
+
+        (token, metadata)
+    }
+
+    /// removes the token and its metadata from the indexes, what is stored beside the token stays
+    /// so nft_burn_call can restore the token while nft_on_burn is pending
+    pub(crate) fn internal_remove_token(&mut self, token_id: &TokenId) -> (Token, TokenMetadata) {
+        let token = self.tokens_by_id.remove(token_id).expect("Token not found");
+        let metadata = self.token_metadata_by_id.remove(token_id).unwrap();
+        self.internal_remove_token_from_owner(&token.owner_id, token_id);
+        if let Some(token_type) = &token.token_type {
+            self.internal_remove_token_from_type(token_type, token_id);
+        }
+
+        (token, metadata)
+    }
+
+    /// per token data kept outside of Token
+    pub(crate) fn internal_clear_token_extras(&mut self, token_id: &TokenId) {
+        self.hidden_tokens.remove(token_id);
+        self.soulbound_tokens.remove(token_id);
+        self.creator_by_id.remove(token_id);
+        self.approvals_expire_at.remove(token_id);
+        self.last_transfer_at.remove(token_id);
+    }
+
+    /// a burned token's media can be minted again into a type that requires unique media
+    pub(crate) fn internal_release_unique_media(&mut self, token: &Token, metadata: &TokenMetadata) {
+        if let (Some(token_type), Some(media)) = (&token.token_type, &metadata.media) {
+            if self.unique_media_types.contains(token_type) {
+                self.unique_media_by_type.remove(&(token_type.clone(), hash_account_id(media)));
+            }
+        }
+    }
+
     pub(crate) fn internal_transfer(
         &mut self,
         sender_id: &AccountId,
@@ -120,18 +338,26 @@ impl Contract {
         token_id: &TokenId,
         approval_id: Option<U64>,
         memo: Option<String>,
-    ) -> Token {
+    ) -> (Token, HashMap<AccountId, u64>) {
         let token = self.tokens_by_id.get(token_id).expect("Token not found");
+        let approvals_expire_at = self.approvals_expire_at.get(token_id).unwrap_or_default();
 
         // CUSTOM - token_type can be locked until unlocked by owner
-        if token.token_type.is_some() {
-            assert_eq!(self.token_types_locked.contains(&token.token_type.clone().unwrap()), false, "Token transfers are locked");
-        }
+        assert!(!self.internal_is_type_locked(&token.token_type), "Token transfers are locked");
+
+        assert!(!self.soulbound_tokens.contains(token_id), "Token is soulbound");
 
         if sender_id != &token.owner_id && !token.approved_account_ids.contains_key(sender_id) {
             env::panic(b"Unauthorized");
         }
 
+        // CUSTOM - approvals can expire
+        if sender_id != &token.owner_id {
+            if let Some(expires_at) = approvals_expire_at.get(sender_id) {
+                assert!(env::block_timestamp() < *expires_at, "Approval expired");
+            }
+        }
+
         // If they included an enforce_approval_id, check the receiver approval id
         if let Some(enforced_approval_id) = approval_id {
             let actual_approval_id = token
@@ -168,11 +394,13 @@ impl Contract {
             token_type: token.token_type.clone(),
         };
         self.tokens_by_id.insert(token_id, &new_token);
+        self.approvals_expire_at.remove(token_id);
+        self.last_transfer_at.insert(token_id, &env::block_timestamp());
 
         if let Some(memo) = memo {
             env::log(format!("Memo: {}", memo).as_bytes());
         }
 
-        token
+        (token, approvals_expire_at)
     }
 }
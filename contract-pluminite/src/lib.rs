@@ -2,26 +2,30 @@ use std::collections::HashMap;
 use std::cmp::min;
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector};
 use near_sdk::json_types::{Base64VecU8, ValidAccountId, U64, U128};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     env, near_bindgen, AccountId, Balance, CryptoHash, PanicOnDefault, Promise, StorageUsage,
 };
 
+use crate::events::*;
 use crate::internal::*;
+pub use crate::burn::*;
 pub use crate::metadata::*;
-pub use crate::mint::*;
 pub use crate::nft_core::*;
 pub use crate::token::*;
-pub use crate::enumerable::*;
 
+mod burn;
+mod events;
 mod internal;
 mod metadata;
 mod mint;
 mod nft_core;
 mod token;
 mod enumerable;
+#[cfg(test)]
+mod tests;
 
 // CUSTOM types
 pub type TokenType = String;
@@ -29,8 +33,18 @@ pub type TypeSupplyCaps = HashMap<TokenType, U64>;
 
 pub const CONTRACT_ROYALTY_CAP: u32 = 1000;
 pub const MINTER_ROYALTY_CAP: u32 = 9000;
+pub const TRANSFER_FEE_CAP: u32 = 500;
 pub const MAX_PROFILE_BIO_LENGTH: usize = 256;
 pub const MAX_PROFILE_IMAGE_LENGTH: usize = 256;
+pub const MAX_PROFILE_IMPORT_BATCH: usize = 50;
+pub const MAX_TOKEN_TYPES: usize = 100;
+pub const MAX_TOKEN_TITLE_LENGTH: usize = 256;
+pub const MAX_TOKEN_DESCRIPTION_LENGTH: usize = 1024;
+pub const MAX_TOKEN_EXTRA_LENGTH: usize = 1024;
+pub const MAX_PAGE_SIZE: u64 = 100;
+pub const MAX_APPROVALS_PER_TOKEN: usize = 10;
+/// supply cap of open edition token types
+pub const UNLIMITED_SUPPLY_CAP: u64 = u64::MAX;
 
 near_sdk::setup_alloc!();
 
@@ -61,15 +75,84 @@ pub struct Contract {
     pub use_storage_fees: bool,
     pub free_mints: u64,
     pub version: u16,
+    pub global_supply_cap: Option<u64>,
+    pub free_mints_enabled: bool,
+    pub unique_media_types: UnorderedSet<TokenType>,
+    pub unique_media_by_type: UnorderedSet<(TokenType, CryptoHash)>,
+    pub migration_log: Vector<(u16, u64)>,
+    pub next_token_id: u64,
+    pub free_mint_blocklist: UnorderedSet<AccountId>,
+    pub free_mint_cooldown_ns: Option<u64>,
+    pub last_free_mint_at: LookupMap<AccountId, u64>,
+    pub free_mints_by_type: LookupMap<TokenType, u64>,
+    pub hidden_tokens: UnorderedSet<TokenId>,
+    pub contract_royalty_by_type: LookupMap<TokenType, u32>,
+    pub profile_accounts: UnorderedSet<AccountId>,
+    pub holders: UnorderedSet<AccountId>,
+    pub type_managers: LookupMap<TokenType, AccountId>,
+    pub require_profile_to_mint: bool,
+    pub metadata_template_by_type: LookupMap<TokenType, TokenMetadata>,
+    pub transfer_fee_bps: u32,
+    pub pending_contract_royalty: Option<u32>,
+    pub global_free_mint_budget: Option<u64>,
+    pub open_edition_until: LookupMap<TokenType, u64>,
+    pub metadata_frozen: bool,
+    pub burn_refund_delay_ns: Option<u64>,
+    pub pending_burn_refunds: LookupMap<AccountId, (Balance, u64)>,
+    pub soulbound_tokens: LookupSet<TokenId>,
+    pub creator_by_id: LookupMap<TokenId, AccountId>,
+    pub approvals_expire_at: LookupMap<TokenId, HashMap<AccountId, u64>>,
+    pub last_transfer_at: LookupMap<TokenId, u64>,
+    pub hidden_profiles: LookupSet<AccountId>,
 }
 
-#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Profile {
     pub bio: String,
     pub image: String,
 }
 
+impl Profile {
+    pub fn assert_valid(&self) {
+        self.assert_valid_bio();
+        self.assert_valid_image();
+    }
+
+    pub fn assert_valid_bio(&self) {
+        assert!(
+            self.bio.len() < MAX_PROFILE_BIO_LENGTH,
+            "Profile bio length is too long"
+        );
+    }
+
+    pub fn assert_valid_image(&self) {
+        assert!(
+            self.image.len() < MAX_PROFILE_IMAGE_LENGTH,
+            "Profile image length is too long"
+        );
+
+        assert!(
+            self.image.is_empty()
+                || ["https://", "ipfs://", "data:image/"].iter().any(|prefix| self.image.starts_with(prefix)),
+            "Profile image must be an https://, ipfs:// or data:image/ URI"
+        );
+    }
+}
+
+/// CUSTOM - everything the admin console shows for a token type
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TypeAdminInfo {
+    pub token_type: TokenType,
+    pub supply_cap: U64,
+    pub minted: U64,
+    pub locked: bool,
+    pub unique_media: bool,
+    pub free_mints: Option<u64>,
+    pub open_edition_until: Option<U64>,
+}
+
 /// Helper structure to for keys of the persistent collections.
 #[derive(BorshSerialize)]
 pub enum StorageKey {
@@ -84,6 +167,25 @@ pub enum StorageKey {
     TokensPerTypeInner { token_type_hash: CryptoHash },
     TokenTypesLocked,
     Profiles,
+    UniqueMediaTypes,
+    UniqueMediaByType,
+    MigrationLog,
+    FreeMintBlocklist,
+    LastFreeMintAt,
+    FreeMintsByType,
+    HiddenTokens,
+    ContractRoyaltyByType,
+    ProfileAccounts,
+    Holders,
+    TypeManagers,
+    MetadataTemplateByType,
+    OpenEditionUntil,
+    PendingBurnRefunds,
+    SoulboundTokens,
+    CreatorById,
+    ApprovalsExpireAt,
+    LastTransferAt,
+    HiddenProfiles,
 }
 
 #[near_bindgen]
@@ -96,6 +198,11 @@ impl Contract {
                free_mints: u64,
                unlocked: Option<bool>,
     ) -> Self {
+        for (token_type, supply_cap) in &supply_cap_by_type {
+            assert!(!token_type.is_empty(), "Token type name cannot be empty");
+            assert!(u64::from(*supply_cap) > 0, "Supply cap must be greater than zero");
+        }
+
         let mut this = Self {
             tokens_per_owner: LookupMap::new(StorageKey::TokensPerOwner.try_to_vec().unwrap()),
             tokens_per_creator: LookupMap::new(StorageKey::TokensPerCreator.try_to_vec().unwrap()),
@@ -117,12 +224,41 @@ impl Contract {
             use_storage_fees,
             free_mints,
             version: 0,
+            global_supply_cap: None,
+            free_mints_enabled: true,
+            unique_media_types: UnorderedSet::new(StorageKey::UniqueMediaTypes.try_to_vec().unwrap()),
+            unique_media_by_type: UnorderedSet::new(StorageKey::UniqueMediaByType.try_to_vec().unwrap()),
+            migration_log: Vector::new(StorageKey::MigrationLog.try_to_vec().unwrap()),
+            next_token_id: 1,
+            free_mint_blocklist: UnorderedSet::new(StorageKey::FreeMintBlocklist.try_to_vec().unwrap()),
+            free_mint_cooldown_ns: None,
+            last_free_mint_at: LookupMap::new(StorageKey::LastFreeMintAt.try_to_vec().unwrap()),
+            free_mints_by_type: LookupMap::new(StorageKey::FreeMintsByType.try_to_vec().unwrap()),
+            hidden_tokens: UnorderedSet::new(StorageKey::HiddenTokens.try_to_vec().unwrap()),
+            contract_royalty_by_type: LookupMap::new(StorageKey::ContractRoyaltyByType.try_to_vec().unwrap()),
+            profile_accounts: UnorderedSet::new(StorageKey::ProfileAccounts.try_to_vec().unwrap()),
+            holders: UnorderedSet::new(StorageKey::Holders.try_to_vec().unwrap()),
+            type_managers: LookupMap::new(StorageKey::TypeManagers.try_to_vec().unwrap()),
+            require_profile_to_mint: false,
+            metadata_template_by_type: LookupMap::new(StorageKey::MetadataTemplateByType.try_to_vec().unwrap()),
+            transfer_fee_bps: 0,
+            pending_contract_royalty: None,
+            global_free_mint_budget: None,
+            open_edition_until: LookupMap::new(StorageKey::OpenEditionUntil.try_to_vec().unwrap()),
+            metadata_frozen: false,
+            burn_refund_delay_ns: None,
+            pending_burn_refunds: LookupMap::new(StorageKey::PendingBurnRefunds.try_to_vec().unwrap()),
+            soulbound_tokens: LookupSet::new(StorageKey::SoulboundTokens.try_to_vec().unwrap()),
+            creator_by_id: LookupMap::new(StorageKey::CreatorById.try_to_vec().unwrap()),
+            approvals_expire_at: LookupMap::new(StorageKey::ApprovalsExpireAt.try_to_vec().unwrap()),
+            last_transfer_at: LookupMap::new(StorageKey::LastTransferAt.try_to_vec().unwrap()),
+            hidden_profiles: LookupSet::new(StorageKey::HiddenProfiles.try_to_vec().unwrap()),
         };
 
         if unlocked.is_none() {
             // CUSTOM - tokens are locked by default
             for token_type in this.supply_cap_by_type.keys() {
-                this.token_types_locked.insert(&token_type);
+                this.token_types_locked.insert(token_type);
             }
         }
 
@@ -154,6 +290,10 @@ impl Contract {
         }
 
         let old_contract: OldContract = env::state_read().expect("Old state doesn't exist");
+        let next_token_id = old_contract.token_metadata_by_id.len() + 1;
+
+        let mut migration_log = Vector::new(StorageKey::MigrationLog.try_to_vec().unwrap());
+        migration_log.push(&(migration_version, env::block_timestamp()));
 
         Self {
             tokens_per_owner: old_contract.tokens_per_owner,
@@ -171,6 +311,114 @@ impl Contract {
             use_storage_fees: old_contract.use_storage_fees,
             free_mints: 3,
             version: migration_version,
+            global_supply_cap: None,
+            free_mints_enabled: true,
+            unique_media_types: UnorderedSet::new(StorageKey::UniqueMediaTypes.try_to_vec().unwrap()),
+            unique_media_by_type: UnorderedSet::new(StorageKey::UniqueMediaByType.try_to_vec().unwrap()),
+            migration_log,
+            next_token_id,
+            free_mint_blocklist: UnorderedSet::new(StorageKey::FreeMintBlocklist.try_to_vec().unwrap()),
+            free_mint_cooldown_ns: None,
+            last_free_mint_at: LookupMap::new(StorageKey::LastFreeMintAt.try_to_vec().unwrap()),
+            free_mints_by_type: LookupMap::new(StorageKey::FreeMintsByType.try_to_vec().unwrap()),
+            hidden_tokens: UnorderedSet::new(StorageKey::HiddenTokens.try_to_vec().unwrap()),
+            contract_royalty_by_type: LookupMap::new(StorageKey::ContractRoyaltyByType.try_to_vec().unwrap()),
+            profile_accounts: UnorderedSet::new(StorageKey::ProfileAccounts.try_to_vec().unwrap()),
+            holders: UnorderedSet::new(StorageKey::Holders.try_to_vec().unwrap()),
+            type_managers: LookupMap::new(StorageKey::TypeManagers.try_to_vec().unwrap()),
+            require_profile_to_mint: false,
+            metadata_template_by_type: LookupMap::new(StorageKey::MetadataTemplateByType.try_to_vec().unwrap()),
+            transfer_fee_bps: 0,
+            pending_contract_royalty: None,
+            global_free_mint_budget: None,
+            open_edition_until: LookupMap::new(StorageKey::OpenEditionUntil.try_to_vec().unwrap()),
+            metadata_frozen: false,
+            burn_refund_delay_ns: None,
+            pending_burn_refunds: LookupMap::new(StorageKey::PendingBurnRefunds.try_to_vec().unwrap()),
+            soulbound_tokens: LookupSet::new(StorageKey::SoulboundTokens.try_to_vec().unwrap()),
+            creator_by_id: LookupMap::new(StorageKey::CreatorById.try_to_vec().unwrap()),
+            approvals_expire_at: LookupMap::new(StorageKey::ApprovalsExpireAt.try_to_vec().unwrap()),
+            last_transfer_at: LookupMap::new(StorageKey::LastTransferAt.try_to_vec().unwrap()),
+            hidden_profiles: LookupSet::new(StorageKey::HiddenProfiles.try_to_vec().unwrap()),
+        }
+    }
+
+    #[init(ignore_state)]
+    pub fn migrate_state_2() -> Self {
+        let migration_version: u16 = 2;
+        assert_eq!(env::predecessor_account_id(), env::current_account_id(), "Private function");
+
+        #[derive(BorshDeserialize)]
+        struct OldContract {
+            tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+            tokens_per_creator: LookupMap<AccountId, UnorderedSet<TokenId>>,
+            tokens_by_id: LookupMap<TokenId, Token>,
+            token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>,
+            owner_id: AccountId,
+            extra_storage_in_bytes_per_token: StorageUsage,
+            metadata: LazyOption<NFTMetadata>,
+            supply_cap_by_type: TypeSupplyCaps,
+            tokens_per_type: LookupMap<TokenType, UnorderedSet<TokenId>>,
+            token_types_locked: UnorderedSet<TokenType>,
+            contract_royalty: u32,
+            profiles: LookupMap<AccountId, Profile>,
+            use_storage_fees: bool,
+            free_mints: u64,
+            version: u16,
+        }
+
+        let old_contract: OldContract = env::state_read().expect("Old state doesn't exist");
+        assert!(old_contract.version < migration_version, "State is already migrated");
+        let next_token_id = old_contract.token_metadata_by_id.len() + 1;
+
+        let mut migration_log = Vector::new(StorageKey::MigrationLog.try_to_vec().unwrap());
+        migration_log.push(&(migration_version, env::block_timestamp()));
+
+        Self {
+            tokens_per_owner: old_contract.tokens_per_owner,
+            tokens_per_creator: old_contract.tokens_per_creator,
+            tokens_by_id: old_contract.tokens_by_id,
+            token_metadata_by_id: old_contract.token_metadata_by_id,
+            owner_id: old_contract.owner_id,
+            extra_storage_in_bytes_per_token: old_contract.extra_storage_in_bytes_per_token,
+            metadata: old_contract.metadata,
+            supply_cap_by_type: old_contract.supply_cap_by_type,
+            tokens_per_type: old_contract.tokens_per_type,
+            token_types_locked: old_contract.token_types_locked,
+            contract_royalty: old_contract.contract_royalty,
+            profiles: old_contract.profiles,
+            use_storage_fees: old_contract.use_storage_fees,
+            free_mints: old_contract.free_mints,
+            version: migration_version,
+            global_supply_cap: None,
+            free_mints_enabled: true,
+            unique_media_types: UnorderedSet::new(StorageKey::UniqueMediaTypes.try_to_vec().unwrap()),
+            unique_media_by_type: UnorderedSet::new(StorageKey::UniqueMediaByType.try_to_vec().unwrap()),
+            migration_log,
+            next_token_id,
+            free_mint_blocklist: UnorderedSet::new(StorageKey::FreeMintBlocklist.try_to_vec().unwrap()),
+            free_mint_cooldown_ns: None,
+            last_free_mint_at: LookupMap::new(StorageKey::LastFreeMintAt.try_to_vec().unwrap()),
+            free_mints_by_type: LookupMap::new(StorageKey::FreeMintsByType.try_to_vec().unwrap()),
+            hidden_tokens: UnorderedSet::new(StorageKey::HiddenTokens.try_to_vec().unwrap()),
+            contract_royalty_by_type: LookupMap::new(StorageKey::ContractRoyaltyByType.try_to_vec().unwrap()),
+            profile_accounts: UnorderedSet::new(StorageKey::ProfileAccounts.try_to_vec().unwrap()),
+            holders: UnorderedSet::new(StorageKey::Holders.try_to_vec().unwrap()),
+            type_managers: LookupMap::new(StorageKey::TypeManagers.try_to_vec().unwrap()),
+            require_profile_to_mint: false,
+            metadata_template_by_type: LookupMap::new(StorageKey::MetadataTemplateByType.try_to_vec().unwrap()),
+            transfer_fee_bps: 0,
+            pending_contract_royalty: None,
+            global_free_mint_budget: None,
+            open_edition_until: LookupMap::new(StorageKey::OpenEditionUntil.try_to_vec().unwrap()),
+            metadata_frozen: false,
+            burn_refund_delay_ns: None,
+            pending_burn_refunds: LookupMap::new(StorageKey::PendingBurnRefunds.try_to_vec().unwrap()),
+            soulbound_tokens: LookupSet::new(StorageKey::SoulboundTokens.try_to_vec().unwrap()),
+            creator_by_id: LookupMap::new(StorageKey::CreatorById.try_to_vec().unwrap()),
+            approvals_expire_at: LookupMap::new(StorageKey::ApprovalsExpireAt.try_to_vec().unwrap()),
+            last_transfer_at: LookupMap::new(StorageKey::LastTransferAt.try_to_vec().unwrap()),
+            hidden_profiles: LookupSet::new(StorageKey::HiddenProfiles.try_to_vec().unwrap()),
         }
     }
 
@@ -178,14 +426,136 @@ impl Contract {
         self.version
     }
 
+    /// nep199 is left out, payouts are only available through nft_transfer_payout
+    pub fn nft_supported_standards(&self) -> Vec<(String, String)> {
+        [("nep171", "1.0.0"), ("nep177", "1.0.0"), ("nep178", "1.0.0"), ("nep181", "1.0.0")]
+            .iter()
+            .map(|(standard, version)| (standard.to_string(), version.to_string()))
+            .collect()
+    }
+
+    /// (version, block timestamp) of each migration applied
+    pub fn get_migration_log(&self) -> Vec<(u16, U64)> {
+        self.migration_log
+            .iter()
+            .map(|(version, timestamp)| (version, U64(timestamp)))
+            .collect()
+    }
+
     pub fn set_use_storage_fees(&mut self, use_storage_fees: bool) {
         assert_eq!(env::predecessor_account_id(), env::current_account_id(), "Private function");
         self.use_storage_fees = use_storage_fees;
     }
 
-    pub fn is_free_mint_available(&self, account_id: AccountId) -> bool {
-        if !self.use_storage_fees {
-            self.get_tokens_created(account_id) < self.free_mints
+    pub fn set_free_mints_enabled(&mut self, free_mints_enabled: bool) {
+        self.assert_owner();
+        self.free_mints_enabled = free_mints_enabled;
+    }
+
+    pub fn set_require_profile_to_mint(&mut self, require_profile_to_mint: bool) {
+        self.assert_owner();
+        self.require_profile_to_mint = require_profile_to_mint;
+    }
+
+    pub fn get_require_profile_to_mint(&self) -> bool {
+        self.require_profile_to_mint
+    }
+
+    /// None means free mints are only limited per account
+    pub fn set_global_free_mint_budget(&mut self, global_free_mint_budget: Option<u64>) {
+        self.assert_owner();
+        self.global_free_mint_budget = global_free_mint_budget;
+    }
+
+    pub fn get_global_free_mints_remaining(&self) -> Option<u64> {
+        self.global_free_mint_budget
+    }
+
+    /// None or 0 refunds burned storage right away
+    pub fn set_burn_refund_delay(&mut self, burn_refund_delay_ns: Option<U64>) {
+        self.assert_owner();
+        self.burn_refund_delay_ns = burn_refund_delay_ns.map(u64::from);
+    }
+
+    pub fn get_burn_refund_delay(&self) -> Option<U64> {
+        self.burn_refund_delay_ns.map(U64)
+    }
+
+    /// (amount, claimable_at) of the storage refund waiting for account_id
+    pub fn get_pending_burn_refund(&self, account_id: AccountId) -> Option<(U128, U64)> {
+        self.pending_burn_refunds
+            .get(&account_id)
+            .map(|(amount, claimable_at)| (U128(amount), U64(claimable_at)))
+    }
+
+    pub fn set_free_mint_cooldown(&mut self, free_mint_cooldown_ns: Option<U64>) {
+        self.assert_owner();
+        self.free_mint_cooldown_ns = free_mint_cooldown_ns.map(u64::from);
+    }
+
+    pub fn get_free_mint_cooldown(&self) -> Option<U64> {
+        self.free_mint_cooldown_ns.map(U64)
+    }
+
+    pub fn add_to_free_mint_blocklist(&mut self, account_id: ValidAccountId) {
+        self.assert_owner();
+        self.free_mint_blocklist.insert(account_id.as_ref());
+    }
+
+    pub fn remove_from_free_mint_blocklist(&mut self, account_id: ValidAccountId) {
+        self.assert_owner();
+        self.free_mint_blocklist.remove(account_id.as_ref());
+    }
+
+    pub fn get_free_mint_blocklist(&self) -> Vec<AccountId> {
+        self.free_mint_blocklist.to_vec()
+    }
+
+    /// CUSTOM - moderation, hidden tokens are left out of enumerations but nft_token and the raw id views still return them
+    pub fn hide_token(&mut self, token_id: TokenId) {
+        self.assert_owner();
+        self.hidden_tokens.insert(&token_id);
+    }
+
+    pub fn unhide_token(&mut self, token_id: TokenId) {
+        self.assert_owner();
+        self.hidden_tokens.remove(&token_id);
+    }
+
+    pub fn get_hidden_tokens(&self) -> Vec<TokenId> {
+        self.hidden_tokens.to_vec()
+    }
+
+    pub fn set_free_mints_for_type(&mut self, token_type: String, free_mints: Option<u64>) {
+        self.assert_owner();
+        if let Some(free_mints) = free_mints {
+            self.free_mints_by_type.insert(&token_type, &free_mints);
+        } else {
+            self.free_mints_by_type.remove(&token_type);
+        }
+    }
+
+    /// free mints for token_type, falls back to the global free_mints
+    pub fn get_free_mints_for_type(&self, token_type: String) -> u64 {
+        self.free_mints_by_type.get(&token_type).unwrap_or(self.free_mints)
+    }
+
+    /// false within the free mint cooldown, mints then pay for their storage
+    pub fn is_free_mint_available(&self, account_id: AccountId, token_type: Option<TokenType>) -> bool {
+        if self.free_mint_blocklist.contains(&account_id) || self.global_free_mint_budget == Some(0) {
+            return false;
+        }
+        if let (Some(free_mint_cooldown_ns), Some(last_free_mint_at)) = (self.free_mint_cooldown_ns, self.last_free_mint_at.get(&account_id)) {
+            if env::block_timestamp() < last_free_mint_at.saturating_add(free_mint_cooldown_ns) {
+                return false;
+            }
+        }
+        if !self.use_storage_fees && self.free_mints_enabled {
+            let free_mints = match token_type {
+                Some(token_type) => self.get_free_mints_for_type(token_type),
+                None => self.free_mints,
+            };
+            self.get_tokens_created(account_id) < free_mints
         } else {
             false
         }
@@ -202,38 +572,145 @@ impl Contract {
         }
     }
 
+    pub fn get_free_mints_used(&self, account_id: AccountId) -> u64 {
+        min(self.get_tokens_created(account_id), self.free_mints)
+    }
+
     pub fn get_free_mints(&self) -> u64 {
         self.free_mints
     }
 
+    pub fn is_free_mint_mode(&self) -> bool {
+        !self.use_storage_fees && self.free_mints > 0 && self.free_mints_enabled
+    }
+
+    pub fn get_free_mints_enabled(&self) -> bool {
+        self.free_mints_enabled
+    }
+
     pub fn get_use_storage_fees(&self) -> bool {
         self.use_storage_fees
     }
 
+    pub fn get_storage_byte_cost(&self) -> U128 {
+        U128(env::storage_byte_cost())
+    }
+
+    pub fn get_extra_storage_in_bytes_per_token(&self) -> U64 {
+        U64(self.extra_storage_in_bytes_per_token)
+    }
+
+    /// estimate of the bytes used by tokens owned by account_id, token and metadata records included
+    pub fn get_account_storage_usage(&self, account_id: AccountId) -> U64 {
+        let tokens = if let Some(tokens) = self.tokens_per_owner.get(&account_id) {
+            tokens
+        } else {
+            return U64(0);
+        };
+        let storage_usage: u64 = tokens
+            .iter()
+            .map(|token_id| {
+                let token = self.tokens_by_id.get(&token_id).unwrap();
+                let metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+                let token_size = token.try_to_vec().unwrap().len() + metadata.try_to_vec().unwrap().len();
+                self.extra_storage_in_bytes_per_token + token_size as u64
+            })
+            .sum();
+        U64(storage_usage)
+    }
+
     pub fn get_profile(&self, account_id: ValidAccountId) -> Option<Profile> {
         let account_id: AccountId = account_id.into();
+        // view calls have no predecessor, so hidden profiles are hidden from everyone here
+        if self.hidden_profiles.contains(&account_id) {
+            return None;
+        }
         self.profiles.get(&account_id)
     }
 
+    /// with use_storage_fees a growing profile must attach a deposit for the added bytes, the excess is refunded
+    #[payable]
     pub fn set_profile(&mut self, profile: Profile) {
-        assert!(
-            profile.bio.len() < MAX_PROFILE_BIO_LENGTH,
-            "Profile bio length is too long"
-        );
+        profile.assert_valid();
+        self.save_profile(profile);
+    }
 
-        assert!(
-            profile.image.len() < MAX_PROFILE_IMAGE_LENGTH,
-            "Profile image length is too long"
-        );
+    pub fn delete_profile(&mut self) {
+        let predecessor_account_id = env::predecessor_account_id();
+        self.profiles.remove(&predecessor_account_id);
+        self.profile_accounts.remove(&predecessor_account_id);
+        self.hidden_profiles.remove(&predecessor_account_id);
+    }
+
+    /// profiles set before profile_accounts existed are not listed until they are updated,
+    /// hidden profiles are left out after paging so from_index counts them too
+    pub fn get_profile_accounts(&self, from_index: U64, limit: u64) -> Vec<AccountId> {
+        let keys = self.profile_accounts.as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        (start..end)
+            .map(|i| keys.get(i).unwrap())
+            .filter(|account_id| !self.hidden_profiles.contains(account_id))
+            .collect()
+    }
+
+    /// CUSTOM - seeds profiles when migrating from another platform, accounts with a profile are skipped
+    pub fn import_profiles(&mut self, entries: Vec<(ValidAccountId, Profile)>) {
+        self.assert_owner();
+        assert!(entries.len() <= MAX_PROFILE_IMPORT_BATCH, "Cannot import more than {} profiles at once", MAX_PROFILE_IMPORT_BATCH);
+        for (account_id, profile) in entries {
+            profile.assert_valid();
+            if self.profiles.get(account_id.as_ref()).is_none() {
+                self.internal_set_profile(account_id.as_ref(), &profile);
+            }
+        }
+    }
 
+    /// visibility is kept apart from the profile so set_profile never changes it
+    pub fn set_profile_hidden(&mut self, hidden: bool) {
         let predecessor_account_id = env::predecessor_account_id();
+        if hidden {
+            self.hidden_profiles.insert(&predecessor_account_id);
+        } else {
+            self.hidden_profiles.remove(&predecessor_account_id);
+        }
+    }
+
+    /// only the changed field is validated, so profiles stored before image URIs were checked can still
+    /// update their bio
+    #[payable]
+    pub fn update_profile_bio(&mut self, bio: String) {
+        let mut profile = self.profiles.get(&env::predecessor_account_id()).unwrap_or_default();
+        profile.bio = bio;
+        profile.assert_valid_bio();
+        self.save_profile(profile);
+    }
 
-        self.profiles.insert(&predecessor_account_id, &profile);
+    #[payable]
+    pub fn update_profile_image(&mut self, image: String) {
+        let mut profile = self.profiles.get(&env::predecessor_account_id()).unwrap_or_default();
+        profile.image = image;
+        profile.assert_valid_image();
+        self.save_profile(profile);
     }
 
+    fn save_profile(&mut self, profile: Profile) {
+        let predecessor_account_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+
+        self.internal_set_profile(&predecessor_account_id, &profile);
+
+        if self.use_storage_fees {
+            refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+        }
+    }
+
+    /// Measured with a 64 char key, the length of the longest valid account id. Upper case makes it
+    /// an invalid account id, so remeasuring can't overwrite the token set of a real (implicit) account.
+    /// The difference to the owner_id length saturates at zero so an owner_id of any length can't underflow it.
     fn measure_min_token_storage_cost(&mut self) {
         let initial_storage_usage = env::storage_usage();
-        let tmp_account_id = "a".repeat(64);
+        let tmp_account_id = "A".repeat(64);
         let u = UnorderedSet::new(
             StorageKey::TokenPerOwnerInner {
                 account_id_hash: hash_account_id(&tmp_account_id),
@@ -242,29 +719,91 @@ impl Contract {
                 .unwrap(),
         );
         self.tokens_per_owner.insert(&tmp_account_id, &u);
+        // a new owner is also added to holders
+        self.holders.insert(&tmp_account_id);
 
-        let tokens_per_owner_entry_in_bytes = env::storage_usage() - initial_storage_usage;
-        let owner_id_extra_cost_in_bytes = (tmp_account_id.len() - self.owner_id.len()) as u64;
+        let owner_entries_in_bytes = env::storage_usage() - initial_storage_usage;
+        let owner_id_extra_cost_in_bytes = tmp_account_id.len().saturating_sub(self.owner_id.len()) as u64;
 
         self.extra_storage_in_bytes_per_token =
-            tokens_per_owner_entry_in_bytes + owner_id_extra_cost_in_bytes;
+            owner_entries_in_bytes + owner_id_extra_cost_in_bytes;
 
         self.tokens_per_owner.remove(&tmp_account_id);
+        self.holders.remove(&tmp_account_id);
     }
 
-    /// CUSTOM - setters for owner
+    // CUSTOM - setters for owner
+
+    pub fn remeasure_storage_cost(&mut self) -> U64 {
+        self.assert_owner();
+        self.measure_min_token_storage_cost();
+        U64(self.extra_storage_in_bytes_per_token)
+    }
 
+    /// 0 leaves the owner out of nft_transfer_payout entirely
     pub fn set_contract_royalty(&mut self, contract_royalty: u32) {
         self.assert_owner();
         assert!(contract_royalty <= CONTRACT_ROYALTY_CAP, "Contract royalties limited to 10% for owner");
         self.contract_royalty = contract_royalty;
     }
 
+    /// two step alternative to set_contract_royalty, applied by confirm_contract_royalty
+    pub fn propose_contract_royalty(&mut self, contract_royalty: u32) {
+        self.assert_owner();
+        assert!(contract_royalty <= CONTRACT_ROYALTY_CAP, "Contract royalties limited to 10% for owner");
+        self.pending_contract_royalty = Some(contract_royalty);
+    }
+
+    pub fn confirm_contract_royalty(&mut self) {
+        self.assert_owner();
+        self.contract_royalty = self.pending_contract_royalty.take().expect("No pending contract royalty");
+    }
+
+    pub fn get_pending_contract_royalty(&self) -> Option<u32> {
+        self.pending_contract_royalty
+    }
+
+    /// basis points of every nft_transfer_payout balance paid to the owner before royalties
+    pub fn set_transfer_fee(&mut self, transfer_fee_bps: u32) {
+        self.assert_owner();
+        assert!(transfer_fee_bps <= TRANSFER_FEE_CAP, "Transfer fee limited to 5%");
+        self.transfer_fee_bps = transfer_fee_bps;
+    }
+
+    pub fn get_transfer_fee(&self) -> u32 {
+        self.transfer_fee_bps
+    }
+
+    /// None falls back to the global contract_royalty
+    pub fn set_contract_royalty_for_type(&mut self, token_type: String, contract_royalty: Option<u32>) {
+        self.assert_owner();
+        if let Some(contract_royalty) = contract_royalty {
+            assert!(contract_royalty <= CONTRACT_ROYALTY_CAP, "Contract royalties limited to 10% for owner");
+            self.contract_royalty_by_type.insert(&token_type, &contract_royalty);
+        } else {
+            self.contract_royalty_by_type.remove(&token_type);
+        }
+    }
+
+    pub fn set_global_supply_cap(&mut self, global_supply_cap: Option<u64>) {
+        self.assert_owner();
+        self.global_supply_cap = global_supply_cap;
+    }
+
     pub fn add_token_types(&mut self, supply_cap_by_type: TypeSupplyCaps, unlocked: Option<bool>) {
         self.assert_owner();
+        assert!(
+            self.supply_cap_by_type.len() + supply_cap_by_type.len() <= MAX_TOKEN_TYPES,
+            "Cannot add more than {} token types",
+            MAX_TOKEN_TYPES
+        );
         for (token_type, hard_cap) in &supply_cap_by_type {
+            assert!(
+                !self.supply_cap_by_type.contains_key(token_type),
+                "Token type already exists, use increase_supply_cap"
+            );
             if unlocked.is_none() {
-                self.token_types_locked.insert(&token_type);
+                self.token_types_locked.insert(token_type);
             }
             self.supply_cap_by_type.insert(token_type.to_string(), *hard_cap);
 
@@ -282,29 +821,344 @@ impl Contract {
         }
     }
 
+    /// CUSTOM - a type manager can lock, unlock and raise the cap of their type only
+    pub fn set_type_manager(&mut self, token_type: String, manager_id: Option<ValidAccountId>) {
+        self.assert_owner();
+        assert!(self.supply_cap_by_type.contains_key(&token_type), "Token type must have supply cap.");
+        if let Some(manager_id) = manager_id {
+            self.type_managers.insert(&token_type, manager_id.as_ref());
+        } else {
+            self.type_managers.remove(&token_type);
+        }
+    }
+
+    pub fn get_type_manager(&self, token_type: String) -> Option<AccountId> {
+        self.type_managers.get(&token_type)
+    }
+
+    pub fn set_metadata_template_for_type(&mut self, token_type: String, template: Option<TokenMetadata>) {
+        self.assert_type_manager_or_owner(&token_type);
+        if let Some(template) = template {
+            if let Err(err) = template.validate() {
+                env::panic(err.as_bytes());
+            }
+            self.metadata_template_by_type.insert(&token_type, &template);
+        } else {
+            self.metadata_template_by_type.remove(&token_type);
+        }
+    }
+
+    pub fn get_metadata_template_for_type(&self, token_type: String) -> Option<TokenMetadata> {
+        self.metadata_template_by_type.get(&token_type)
+    }
+
+    pub fn increase_supply_cap(&mut self, token_type: String, supply_cap: U64) {
+        self.assert_type_manager_or_owner(&token_type);
+        let current_cap = self.supply_cap_by_type.get(&token_type).expect("Token type must have supply cap.");
+        assert!(
+            u64::from(supply_cap) > u64::from(*current_cap),
+            "New supply cap must be greater than the current one"
+        );
+        self.supply_cap_by_type.insert(token_type, supply_cap);
+    }
+
+    /// CUSTOM - the type mints without a cap until the deadline, finalize_open_edition then caps it at the minted supply
+    pub fn set_open_edition(&mut self, token_type: String, until: U64) {
+        self.assert_type_manager_or_owner(&token_type);
+        assert!(self.supply_cap_by_type.contains_key(&token_type), "Token type must have supply cap.");
+        assert!(u64::from(until) > env::block_timestamp(), "Open edition deadline must be in the future");
+        self.open_edition_until.insert(&token_type, &until.into());
+        self.supply_cap_by_type.insert(token_type, U64(UNLIMITED_SUPPLY_CAP));
+    }
+
+    pub fn get_open_edition_until(&self, token_type: String) -> Option<U64> {
+        self.open_edition_until.get(&token_type).map(U64)
+    }
+
+    pub fn finalize_open_edition(&mut self, token_type: String) {
+        let until = self.open_edition_until.get(&token_type).expect("Token type is not an open edition");
+        assert!(env::block_timestamp() >= until, "Open edition has not ended yet");
+        let minted = self.nft_supply_for_type(token_type.clone());
+        self.supply_cap_by_type.insert(token_type.clone(), minted);
+        self.open_edition_until.remove(&token_type);
+    }
+
+    pub fn set_unique_media_for_type(&mut self, token_type: String, enabled: bool) {
+        self.assert_owner();
+        assert!(self.supply_cap_by_type.contains_key(&token_type), "Token type must have supply cap.");
+        if enabled {
+            self.unique_media_types.insert(&token_type);
+        } else {
+            self.unique_media_types.remove(&token_type);
+        }
+    }
+
+    pub fn lock_token_types(&mut self, token_types: Vec<String>) {
+        for token_type in &token_types {
+            self.assert_type_manager_or_owner(token_type);
+            assert!(self.supply_cap_by_type.contains_key(token_type), "Token type must have supply cap.");
+            self.token_types_locked.insert(token_type);
+        }
+    }
+
     pub fn unlock_token_types(&mut self, token_types: Vec<String>) {
         for token_type in &token_types {
-            self.token_types_locked.remove(&token_type);
+            self.assert_type_manager_or_owner(token_type);
+            self.token_types_locked.remove(token_type);
         }
     }
 
-    /// CUSTOM - views
+    /// re-inserts each token of the page into its type set, safe to run repeatedly
+    pub fn rebuild_tokens_per_type(&mut self, from_index: U64, limit: u64) {
+        self.assert_owner();
+        let keys = self.token_metadata_by_id.keys_as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        let token_ids: Vec<TokenId> = (start..end).map(|i| keys.get(i).unwrap()).collect();
+        for token_id in token_ids {
+            if let Some(token_type) = self.tokens_by_id.get(&token_id).and_then(|token| token.token_type) {
+                self.internal_add_token_to_type(&token_type, &token_id);
+            }
+        }
+    }
+
+    /// moves every token and per-type setting of old_token_type to new_token_type in one call
+    pub fn rename_token_type(&mut self, old_token_type: String, new_token_type: String) {
+        self.assert_owner();
+        assert!(!new_token_type.is_empty(), "Token type name cannot be empty");
+        assert!(!self.supply_cap_by_type.contains_key(&new_token_type), "Token type already exists");
+        let supply_cap = self.supply_cap_by_type.remove(&old_token_type).expect("Token type must have supply cap.");
+        self.supply_cap_by_type.insert(new_token_type.clone(), supply_cap);
+
+        let unique_media = self.unique_media_types.remove(&old_token_type);
+        if unique_media {
+            self.unique_media_types.insert(&new_token_type);
+        }
+        if self.token_types_locked.remove(&old_token_type) {
+            self.token_types_locked.insert(&new_token_type);
+        }
+
+        if let Some(mut tokens) = self.tokens_per_type.remove(&old_token_type) {
+            let token_ids = tokens.to_vec();
+            tokens.clear();
+            for token_id in token_ids {
+                let mut token = self.tokens_by_id.get(&token_id).unwrap();
+                token.token_type = Some(new_token_type.clone());
+                self.tokens_by_id.insert(&token_id, &token);
+                self.internal_add_token_to_type(&new_token_type, &token_id);
+                if unique_media {
+                    if let Some(media) = self.token_metadata_by_id.get(&token_id).unwrap().media {
+                        let media_hash = hash_account_id(&media);
+                        self.unique_media_by_type.remove(&(old_token_type.clone(), media_hash));
+                        self.unique_media_by_type.insert(&(new_token_type.clone(), media_hash));
+                    }
+                }
+            }
+        }
+
+        if let Some(free_mints) = self.free_mints_by_type.remove(&old_token_type) {
+            self.free_mints_by_type.insert(&new_token_type, &free_mints);
+        }
+        if let Some(contract_royalty) = self.contract_royalty_by_type.remove(&old_token_type) {
+            self.contract_royalty_by_type.insert(&new_token_type, &contract_royalty);
+        }
+        if let Some(manager_id) = self.type_managers.remove(&old_token_type) {
+            self.type_managers.insert(&new_token_type, &manager_id);
+        }
+        if let Some(template) = self.metadata_template_by_type.remove(&old_token_type) {
+            self.metadata_template_by_type.insert(&new_token_type, &template);
+        }
+        if let Some(until) = self.open_edition_until.remove(&old_token_type) {
+            self.open_edition_until.insert(&new_token_type, &until);
+        }
+    }
+
+    /// adds the owners of the page to holders, safe to run repeatedly
+    pub fn rebuild_holders(&mut self, from_index: U64, limit: u64) {
+        self.assert_owner();
+        let keys = self.token_metadata_by_id.keys_as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        for i in start..end {
+            let owner_id = self.tokens_by_id.get(&keys.get(i).unwrap()).unwrap().owner_id;
+            self.holders.insert(&owner_id);
+        }
+    }
+
+    /// records account_id as the creator of the live tokens of its creator set that have none yet, safe to run repeatedly
+    pub fn backfill_token_creators(&mut self, account_id: AccountId, from_index: U64, limit: u64) {
+        self.assert_owner();
+        let tokens = self.tokens_per_creator.get(&account_id).expect("No tokens for creator");
+        let keys = tokens.as_vector();
+        let start = u64::from(from_index);
+        let end = min(start.saturating_add(limit), keys.len());
+        for i in start..end {
+            let token_id = keys.get(i).unwrap();
+            if self.tokens_by_id.contains_key(&token_id) && !self.creator_by_id.contains_key(&token_id) {
+                self.creator_by_id.insert(&token_id, &account_id);
+            }
+        }
+    }
+
+    /// logs a token_types_unlocked event listing the types that were locked
+    pub fn unlock_all_token_types(&mut self) {
+        self.assert_owner();
+        let token_types = self.token_types_locked.to_vec();
+        self.token_types_locked.clear();
+        emit_token_types_unlocked(token_types);
+    }
+
+    // CUSTOM - views
 
     pub fn get_contract_royalty(&self) -> u32 {
         self.contract_royalty
     }
 
+    pub fn get_contract_royalty_for_type(&self, token_type: String) -> u32 {
+        self.internal_contract_royalty(&Some(token_type))
+    }
+
+    pub fn get_global_supply_cap(&self) -> Option<u64> {
+        self.global_supply_cap
+    }
+
+    /// block timestamp in nanoseconds, for clients aligning timed mints
+    pub fn get_current_timestamp(&self) -> U64 {
+        U64(env::block_timestamp())
+    }
+
+    /// id assigned by nft_mint when no token_id is given
+    pub fn get_next_token_id(&self) -> U64 {
+        U64(self.next_token_id)
+    }
+
     pub fn get_supply_caps(&self) -> TypeSupplyCaps {
         self.supply_cap_by_type.clone()
     }
 
+    pub fn can_mint(&self, token_type: String, account_id: AccountId) -> bool {
+        self.internal_check_mint(Some(&token_type), &account_id).is_ok()
+    }
+
+    /// None for token types with UNLIMITED_SUPPLY_CAP
+    pub fn get_remaining_supply(&self, token_type: String) -> Option<U64> {
+        let cap = match self.supply_cap_by_type.get(&token_type) {
+            Some(cap) => u64::from(*cap),
+            None => return Some(U64(0)),
+        };
+        if self.internal_is_open_edition_ended(&token_type) {
+            return Some(U64(0));
+        }
+        if cap == UNLIMITED_SUPPLY_CAP {
+            return None;
+        }
+        Some(U64(cap.saturating_sub(self.nft_supply_for_type(token_type).into())))
+    }
+
+    /// false for unknown token types, true for an ended open edition
+    pub fn nft_type_sold_out(&self, token_type: String) -> bool {
+        match self.supply_cap_by_type.get(&token_type) {
+            Some(_) if self.internal_is_open_edition_ended(&token_type) => true,
+            Some(cap) => u64::from(self.nft_supply_for_type(token_type.clone())) >= u64::from(*cap),
+            None => false,
+        }
+    }
+
+    /// types anyone can mint into right now by the checks of nft_mint, profile requirements aside
+    pub fn currently_mintable_types(&self) -> Vec<String> {
+        self.supply_cap_by_type
+            .keys()
+            .filter(|token_type| self.internal_check_mint_type(Some(token_type), None).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_token_type_count(&self) -> u64 {
+        self.supply_cap_by_type.len() as u64
+    }
+
+    /// dry run of nft_mint for account_id, returns the first failing check
+    pub fn validate_mint(
+        &self,
+        token_type: String,
+        metadata: TokenMetadata,
+        account_id: AccountId,
+    ) -> Result<(), String> {
+        self.internal_check_mint(Some(&token_type), &account_id)?;
+        metadata.validate()
+    }
+
+    pub fn token_type_exists(&self, token_type: String) -> bool {
+        self.supply_cap_by_type.contains_key(&token_type)
+    }
+
+    pub fn get_type_admin_view(&self) -> Vec<TypeAdminInfo> {
+        self.supply_cap_by_type
+            .iter()
+            .map(|(token_type, supply_cap)| TypeAdminInfo {
+                token_type: token_type.clone(),
+                supply_cap: *supply_cap,
+                minted: self.nft_supply_for_type(token_type.clone()),
+                locked: self.token_types_locked.contains(token_type),
+                unique_media: self.unique_media_types.contains(token_type),
+                free_mints: self.free_mints_by_type.get(token_type),
+                open_edition_until: self.get_open_edition_until(token_type.clone()),
+            })
+            .collect()
+    }
+
     pub fn get_token_types_locked(&self) -> Vec<String> {
         self.token_types_locked.to_vec()
     }
 
+    pub fn get_unique_media_types(&self) -> Vec<String> {
+        self.unique_media_types.to_vec()
+    }
+
+    pub fn nft_token_exists(&self, token_id: TokenId) -> bool {
+        self.tokens_by_id.contains_key(&token_id)
+    }
+
+    pub fn nft_token_creator(&self, token_id: TokenId) -> Option<AccountId> {
+        self.creator_by_id.get(&token_id)
+    }
+
+    pub fn nft_token_full(&self, token_id: TokenId) -> Option<TokenFull> {
+        let token = self.nft_token(token_id.clone())?;
+        let remaining_supply = match &token.token_type {
+            Some(token_type) => self.get_remaining_supply(token_type.clone()),
+            None => None,
+        };
+        Some(TokenFull {
+            locked: self.internal_is_type_locked(&token.token_type),
+            remaining_supply,
+            last_transfer_at: self.nft_token_last_transfer(token_id),
+            token,
+        })
+    }
+
+    /// the mint time until the first transfer, None for tokens minted before transfers were recorded
+    pub fn nft_token_last_transfer(&self, token_id: TokenId) -> Option<U64> {
+        self.last_transfer_at.get(&token_id).map(U64)
+    }
+
+    pub fn nft_token_royalty(&self, token_id: TokenId) -> Option<HashMap<AccountId, u32>> {
+        self.tokens_by_id.get(&token_id).map(|token| token.royalty)
+    }
+
+    /// false for missing and untyped tokens, in the order of token_ids
+    pub fn nft_tokens_locked_status(&self, token_ids: Vec<TokenId>) -> Vec<bool> {
+        token_ids
+            .iter()
+            .map(|token_id| {
+                matches!(self.tokens_by_id.get(token_id), Some(token) if self.internal_is_type_locked(&token.token_type))
+            })
+            .collect()
+    }
+
     pub fn is_token_locked(&self, token_id: TokenId) -> bool {
         let token = self.tokens_by_id.get(&token_id).expect("No token");
-        assert_eq!(token.token_type.is_some(), true, "Token must have type");
+        assert!(token.token_type.is_some(), "Token must have type");
         let token_type = token.token_type.unwrap();
         self.token_types_locked.contains(&token_type)
     }
@@ -29,6 +29,43 @@ pub struct TokenMetadata {
     pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
 }
 
+impl TokenMetadata {
+    /// CUSTOM - metadata checks enforced by nft_mint
+    pub fn validate(&self) -> Result<(), String> {
+        if matches!(&self.title, Some(title) if title.len() >= MAX_TOKEN_TITLE_LENGTH) {
+            return Err("Token title length is too long".to_string());
+        }
+        if matches!(&self.description, Some(description) if description.len() >= MAX_TOKEN_DESCRIPTION_LENGTH) {
+            return Err("Token description length is too long".to_string());
+        }
+        if matches!(&self.extra, Some(extra) if extra.len() >= MAX_TOKEN_EXTRA_LENGTH) {
+            return Err("Token extra length is too long".to_string());
+        }
+        if matches!(&self.media_hash, Some(media_hash) if media_hash.0.len() != 32) {
+            return Err("Media hash has to be a 32 bytes sha256 hash".to_string());
+        }
+        Ok(())
+    }
+
+    /// CUSTOM - fields left unset are taken from the token type template
+    pub fn with_template(self, template: TokenMetadata) -> Self {
+        TokenMetadata {
+            title: self.title.or(template.title),
+            description: self.description.or(template.description),
+            media: self.media.or(template.media),
+            media_hash: self.media_hash.or(template.media_hash),
+            copies: self.copies.or(template.copies),
+            issued_at: self.issued_at.or(template.issued_at),
+            expires_at: self.expires_at.or(template.expires_at),
+            starts_at: self.starts_at.or(template.starts_at),
+            updated_at: self.updated_at.or(template.updated_at),
+            extra: self.extra.or(template.extra),
+            reference: self.reference.or(template.reference),
+            reference_hash: self.reference_hash.or(template.reference_hash),
+        }
+    }
+}
+
 pub trait NonFungibleTokenMetadata {
     fn nft_metadata(&self) -> NFTMetadata;
 }
@@ -39,3 +76,23 @@ impl NonFungibleTokenMetadata for Contract {
         self.metadata.get().unwrap()
     }
 }
+
+/// CUSTOM - owner can update the contract metadata until it is frozen
+#[near_bindgen]
+impl Contract {
+    pub fn nft_set_metadata(&mut self, metadata: NFTMetadata) {
+        self.assert_owner();
+        assert!(!self.metadata_frozen, "Contract metadata is frozen");
+        self.metadata.set(&metadata);
+    }
+
+    /// irreversible
+    pub fn freeze_contract_metadata(&mut self) {
+        self.assert_owner();
+        self.metadata_frozen = true;
+    }
+
+    pub fn is_contract_metadata_frozen(&self) -> bool {
+        self.metadata_frozen
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
 use crate::*;
 
 #[near_bindgen]
@@ -10,9 +12,109 @@ impl Contract {
         perpetual_royalties: Option<HashMap<AccountId, u32>>,
         receiver_id: Option<ValidAccountId>,
         token_type: Option<TokenType>,
+        transferable: Option<bool>,
+        creator_royalty: Option<u32>,
+    ) {
+        self.internal_mint(token_id, metadata, perpetual_royalties, receiver_id, token_type, transferable, creator_royalty, None);
+    }
+
+    /// CUSTOM - owner backfill of historical tokens, issued_at is in milliseconds like the block time stored by nft_mint
+    #[payable]
+    pub fn nft_mint_backfill(
+        &mut self,
+        token_id: Option<TokenId>,
+        metadata: TokenMetadata,
+        perpetual_royalties: Option<HashMap<AccountId, u32>>,
+        receiver_id: Option<ValidAccountId>,
+        token_type: Option<TokenType>,
+        transferable: Option<bool>,
+        creator_royalty: Option<u32>,
+        issued_at: Option<U64>,
+    ) {
+        self.assert_owner();
+        self.internal_mint(token_id, metadata, perpetual_royalties, receiver_id, token_type, transferable, creator_royalty, issued_at.map(u64::from));
+    }
+
+    /// CUSTOM - owner mints from the reserve of a type and leaves the type locked, whatever its state before
+    #[payable]
+    pub fn mint_reserved_and_lock(
+        &mut self,
+        token_id: Option<TokenId>,
+        metadata: TokenMetadata,
+        receiver_id: Option<ValidAccountId>,
+        token_type: TokenType,
+    ) {
+        self.assert_owner();
+        self.internal_mint(token_id, metadata, None, receiver_id, Some(token_type.clone()), None, None, None);
+        self.token_types_locked.insert(&token_type);
+    }
+
+    /// CUSTOM - creator replaces the metadata of a token they still own, settling the storage difference,
+    /// the type template fills unset fields like on mint and issued_at is kept
+    #[payable]
+    pub fn nft_remint(
+        &mut self,
+        token_id: TokenId,
+        new_metadata: TokenMetadata,
     ) {
+        assert_at_least_one_yocto();
+        let token = self.tokens_by_id.get(&token_id).expect("Token not found");
+        let predecessor_account_id = env::predecessor_account_id();
+        assert_eq!(Some(&predecessor_account_id), self.creator_by_id.get(&token_id).as_ref(), "Only the creator can remint");
+        assert_eq!(predecessor_account_id, token.owner_id, "Creator must still own the token");
+        let mut new_metadata = match token.token_type.as_ref().and_then(|token_type| self.metadata_template_by_type.get(token_type)) {
+            Some(template) => new_metadata.with_template(template),
+            None => new_metadata,
+        };
+        if let Err(err) = new_metadata.validate() {
+            env::panic(err.as_bytes());
+        }
+
+        let initial_storage_usage = env::storage_usage();
+        let old_metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+        new_metadata.issued_at = old_metadata.issued_at.clone();
 
-        let mut final_token_id = format!("{}", self.token_metadata_by_id.len() + 1);
+        if let Some(token_type) = &token.token_type {
+            if self.unique_media_types.contains(token_type) && old_metadata.media != new_metadata.media {
+                if let Some(media) = &old_metadata.media {
+                    self.unique_media_by_type.remove(&(token_type.clone(), hash_account_id(media)));
+                }
+                if let Some(media) = &new_metadata.media {
+                    assert!(
+                        self.unique_media_by_type.insert(&(token_type.clone(), hash_account_id(media))),
+                        "Media already minted for token type"
+                    );
+                }
+            }
+        }
+
+        self.token_metadata_by_id.insert(&token_id, &new_metadata);
+
+        let final_storage_usage = env::storage_usage();
+        if !self.use_storage_fees {
+            refund_deposit(0);
+        } else if final_storage_usage > initial_storage_usage {
+            refund_deposit(final_storage_usage - initial_storage_usage);
+        } else {
+            refund_deposit(0);
+            refund_released_storage(predecessor_account_id, initial_storage_usage - final_storage_usage);
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_mint(
+        &mut self,
+        token_id: Option<TokenId>,
+        mut metadata: TokenMetadata,
+        perpetual_royalties: Option<HashMap<AccountId, u32>>,
+        receiver_id: Option<ValidAccountId>,
+        token_type: Option<TokenType>,
+        transferable: Option<bool>,
+        creator_royalty: Option<u32>,
+        issued_at: Option<u64>,
+    ) {
+        let mut final_token_id = format!("{}", self.next_token_id);
         if let Some(token_id) = token_id {
             final_token_id = token_id
         }
@@ -22,14 +124,18 @@ impl Contract {
             owner_id = receiver_id.into();
         }
 
-        let pay_for_storage =  self.use_storage_fees || !self.is_free_mint_available(owner_id.clone());
+        let pay_for_storage =  self.use_storage_fees || !self.is_free_mint_available(owner_id.clone(), token_type.clone());
+        let initial_storage_usage = env::storage_usage();
 
-        let initial_storage_usage = if pay_for_storage {
-            env::storage_usage()
+        // CUSTOM - free mints draw from the global budget and are rate limited per account when a cooldown is set
+        if !pay_for_storage {
+            if let Some(global_free_mint_budget) = self.global_free_mint_budget.as_mut() {
+                *global_free_mint_budget -= 1;
+            }
+            if self.free_mint_cooldown_ns.is_some() {
+                self.last_free_mint_at.insert(&owner_id, &env::block_timestamp());
+            }
         }
-        else {
-            0
-        };
 
         // CUSTOM - create royalty map
         let mut royalty = HashMap::new();
@@ -38,33 +144,47 @@ impl Contract {
         if let Some(perpetual_royalties) = perpetual_royalties {
             assert!(perpetual_royalties.len() < 7, "Cannot add more than 6 perpetual royalty amounts");
             for (account, amount) in perpetual_royalties {
+                assert!(env::is_valid_account_id(account.as_bytes()), "Invalid royalty account id {}", account);
                 royalty.insert(account, amount);
                 total_perpetual += amount;
             }
         }
+        // minter can include themselves in the royalty split
+        if let Some(creator_royalty) = creator_royalty {
+            if let Some(amount) = royalty.insert(env::predecessor_account_id(), creator_royalty) {
+                total_perpetual -= amount;
+            }
+            total_perpetual += creator_royalty;
+        }
         // royalty limit for minter capped at 90%
         assert!(total_perpetual <= MINTER_ROYALTY_CAP, "Perpetual royalties cannot be more than 90%");
 
-        // CUSTOM - enforce minting caps by token_type 
+        if let Some(template) = token_type.as_ref().and_then(|token_type| self.metadata_template_by_type.get(token_type)) {
+            metadata = metadata.with_template(template);
+        }
+
+        // CUSTOM - enforce global and token_type minting caps and locks
+        if let Err(err) = self.internal_check_mint(token_type.as_ref(), &env::predecessor_account_id()) {
+            env::panic(err.as_bytes());
+        }
+        if let Err(err) = metadata.validate() {
+            env::panic(err.as_bytes());
+        }
+        // issued_at is the mint time in milliseconds unless backfilled by the owner
+        metadata.issued_at = Some(issued_at.unwrap_or(env::block_timestamp() / 1_000_000).to_string());
+
         if token_type.is_some() {
             let token_type = token_type.clone().unwrap();
-            let cap = u64::from(*self.supply_cap_by_type.get(&token_type).expect("Token type must have supply cap."));
-            let supply = u64::from(self.nft_supply_for_type(&token_type));
-            assert!(supply < cap, "Cannot mint anymore of token type.");
-            let mut tokens_per_type = self
-                .tokens_per_type
-                .get(&token_type)
-                .unwrap_or_else(|| {
-                    UnorderedSet::new(
-                        StorageKey::TokensPerTypeInner {
-                            token_type_hash: hash_account_id(&token_type),
-                        }
-                        .try_to_vec()
-                        .unwrap(),
-                    )
-                });
-            tokens_per_type.insert(&final_token_id);
-            self.tokens_per_type.insert(&token_type, &tokens_per_type);
+            // reject media already minted within a type that requires unique media
+            if self.unique_media_types.contains(&token_type) {
+                if let Some(media) = &metadata.media {
+                    assert!(
+                        self.unique_media_by_type.insert(&(token_type.clone(), hash_account_id(media))),
+                        "Media already minted for token type"
+                    );
+                }
+            }
+            self.internal_add_token_to_type(&token_type, &final_token_id);
         }
         // END CUSTOM
 
@@ -75,11 +195,22 @@ impl Contract {
             royalty,
             token_type,
         };
+        let token_storage_usage = env::storage_usage();
         assert!(
             self.tokens_by_id.insert(&final_token_id, &token).is_none(),
             "Token already exists"
         );
         self.token_metadata_by_id.insert(&final_token_id, &metadata);
+        let token_size_in_bytes = env::storage_usage() - token_storage_usage;
+        // CUSTOM - soulbound tokens can't be transferred
+        if !transferable.unwrap_or(true) {
+            self.soulbound_tokens.insert(&final_token_id);
+        }
+        self.creator_by_id.insert(&final_token_id, &owner_id);
+        // mint counts as the first ownership change
+        self.last_transfer_at.insert(&final_token_id, &env::block_timestamp());
+        // counted on every mint so burned ids are never handed out again
+        self.next_token_id += 1;
         self.internal_add_token_to_owner(&token.owner_id, &final_token_id);
 
         match self.tokens_per_creator.get(&owner_id.clone()) {
@@ -100,12 +231,15 @@ impl Contract {
             }
         }
 
+        let new_token_size_in_bytes = env::storage_usage() - initial_storage_usage;
         if pay_for_storage {
-            let new_token_size_in_bytes = env::storage_usage() - initial_storage_usage;
             let required_storage_in_bytes =
                 self.extra_storage_in_bytes_per_token + new_token_size_in_bytes;
 
             refund_deposit(required_storage_in_bytes);
+        } else {
+            // CUSTOM - free mints cover the token and its metadata, the minter still pays for the index entries
+            refund_deposit(new_token_size_in_bytes - token_size_in_bytes);
         }
     }
-}
\ No newline at end of file
+}
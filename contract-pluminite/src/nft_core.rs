@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
 use crate::*;
 use near_sdk::json_types::{ValidAccountId, U64};
 use near_sdk::{ext_contract, log, Gas, PromiseResult};
@@ -36,7 +38,21 @@ pub trait NonFungibleTokenCore {
         msg: String,
     ) -> Promise;
 
-    fn nft_approve(&mut self, token_id: TokenId, account_id: ValidAccountId, msg: Option<String>);
+    /// Returns the approval id assigned to `account_id`. CUSTOM - the approval can't be used after `expires_at`.
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: ValidAccountId,
+        msg: Option<String>,
+        expires_at: Option<U64>,
+    ) -> u64;
+
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: AccountId,
+        approval_id: Option<U64>,
+    ) -> bool;
 
     fn nft_revoke(&mut self, token_id: TokenId, account_id: ValidAccountId);
 
@@ -79,16 +95,20 @@ trait NonFungibleTokenResolver {
         owner_id: AccountId,
         receiver_id: AccountId,
         approved_account_ids: HashMap<AccountId, U64>,
+        approvals_expire_at: HashMap<AccountId, u64>,
         token_id: TokenId,
     ) -> bool;
 }
 
-trait NonFungibleTokenResolver {
+pub trait NonFungibleTokenResolver {
+    /// Returns `true` if the token was kept by `receiver_id`. If `nft_on_transfer` returned
+    /// `true` or failed, ownership and approvals are restored to `owner_id` and `false` is returned.
     fn nft_resolve_transfer(
         &mut self,
         owner_id: AccountId,
         receiver_id: AccountId,
         approved_account_ids: HashMap<AccountId, U64>,
+        approvals_expire_at: HashMap<AccountId, u64>,
         token_id: TokenId,
     ) -> bool;
 }
@@ -105,13 +125,12 @@ impl NonFungibleTokenCore for Contract {
     ) {
         assert_one_yocto();
 
-        let token_data = self.nft_token(token_id.clone());
-        if let Some(token_data_unwrapped) = token_data {
-            assert!(token_data_unwrapped.approved_account_ids.len() == 0, "Token already approved on a marketplace. Abort");
+        if let Some(token) = self.tokens_by_id.get(&token_id) {
+            assert!(token.approved_account_ids.is_empty(), "Token already approved on a marketplace. Abort");
         }
 
         let sender_id = env::predecessor_account_id();
-        let previous_token = self.internal_transfer(
+        let (previous_token, approvals_expire_at) = self.internal_transfer(
             &sender_id,
             receiver_id.as_ref(),
             &token_id,
@@ -122,6 +141,7 @@ impl NonFungibleTokenCore for Contract {
             refund_approved_account_ids(
                 previous_token.owner_id.clone(),
                 &previous_token.approved_account_ids,
+                &approvals_expire_at,
             );
         }
     }
@@ -139,7 +159,7 @@ impl NonFungibleTokenCore for Contract {
     ) -> Option<Payout> {
         assert_one_yocto();
         let sender_id = env::predecessor_account_id();
-        let previous_token = self.internal_transfer(
+        let (previous_token, approvals_expire_at) = self.internal_transfer(
             &sender_id,
             receiver_id.as_ref(),
             &token_id,
@@ -150,6 +170,7 @@ impl NonFungibleTokenCore for Contract {
             refund_approved_account_ids(
                 previous_token.owner_id.clone(),
                 &previous_token.approved_account_ids,
+                &approvals_expire_at,
             );
         }
 
@@ -158,9 +179,17 @@ impl NonFungibleTokenCore for Contract {
         let owner_id = previous_token.owner_id;
         let mut total_perpetual = 0;
         let payout = if let Some(balance) = balance {
-            let balance_u128 = u128::from(balance);
+            // CUSTOM - the transfer fee goes to the contract owner, royalties are computed on what is left
+            let transfer_fee = if self.transfer_fee_bps > 0 && self.owner_id != owner_id {
+                u128::from(royalty_to_payout(self.transfer_fee_bps, balance.into()))
+            } else {
+                0
+            };
+            let balance_u128 = u128::from(balance) - transfer_fee;
             let mut payout: Payout = HashMap::new();
-            let royalty = self.tokens_by_id.get(&token_id).expect("No token").royalty;
+            let token = self.tokens_by_id.get(&token_id).expect("No token");
+            let royalty = token.royalty;
+            let contract_royalty = self.internal_contract_royalty(&token.token_type);
 
             if let Some(max_len_payout) = max_len_payout {
                 assert!(royalty.len() as u32 <= max_len_payout, "Market cannot payout to that many receivers");
@@ -168,20 +197,25 @@ impl NonFungibleTokenCore for Contract {
 
             for (k, v) in royalty.iter() {
                 let key = k.clone();
-                if key != owner_id {
+                // zero royalties are omitted to keep the payout map compact
+                if key != owner_id && *v > 0 {
                     payout.insert(key, royalty_to_payout(*v, balance_u128));
                     total_perpetual += *v;
                 }
             }
 
             // payout to contract owner - may be previous token owner, they get remainder of balance
-            if self.contract_royalty > 0 && self.owner_id != owner_id {
-                payout.insert(self.owner_id.clone(), royalty_to_payout(self.contract_royalty, balance_u128));
-                total_perpetual += self.contract_royalty;
+            if contract_royalty > 0 && self.owner_id != owner_id {
+                payout.insert(self.owner_id.clone(), royalty_to_payout(contract_royalty, balance_u128));
+                total_perpetual += contract_royalty;
+            }
+            if transfer_fee > 0 {
+                payout.entry(self.owner_id.clone()).or_insert(U128(0)).0 += transfer_fee;
             }
             assert!(total_perpetual <= MINTER_ROYALTY_CAP + CONTRACT_ROYALTY_CAP, "Royalties should not be more than caps");
-            // payout to previous owner
-            payout.insert(owner_id, royalty_to_payout(10000 - total_perpetual, balance_u128));
+            // payout to previous owner, including the rounding remainder so the payouts add up to the balance
+            let paid_out: u128 = payout.values().map(|amount| amount.0).sum();
+            payout.insert(owner_id, U128(u128::from(balance) - paid_out));
 
             Some(payout)
         } else {
@@ -202,7 +236,7 @@ impl NonFungibleTokenCore for Contract {
     ) -> Promise {
         assert_one_yocto();
         let sender_id = env::predecessor_account_id();
-        let previous_token = self.internal_transfer(
+        let (previous_token, approvals_expire_at) = self.internal_transfer(
             &sender_id,
             receiver_id.as_ref(),
             &token_id,
@@ -223,6 +257,7 @@ impl NonFungibleTokenCore for Contract {
                 previous_token.owner_id,
                 receiver_id.into(),
                 previous_token.approved_account_ids,
+                approvals_expire_at,
                 token_id,
                 &env::current_account_id(),
                 NO_DEPOSIT,
@@ -231,7 +266,13 @@ impl NonFungibleTokenCore for Contract {
     }
 
     #[payable]
-    fn nft_approve(&mut self, token_id: TokenId, account_id: ValidAccountId, msg: Option<String>) {
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: ValidAccountId,
+        msg: Option<String>,
+        expires_at: Option<U64>,
+    ) -> u64 {
         if self.use_storage_fees {
             assert_at_least_one_yocto();
         } else {
@@ -239,57 +280,45 @@ impl NonFungibleTokenCore for Contract {
         }
 
         let account_id: AccountId = account_id.into();
-
-        let mut token = self.tokens_by_id.get(&token_id).expect("Token not found");
-
-        assert_eq!(
-            &env::predecessor_account_id(),
-            &token.owner_id,
-            "Predecessor must be the token owner."
+        let (approval_id, storage_used) = self.internal_approve(
+            &token_id,
+            &account_id,
+            expires_at.map(u64::from),
+            msg,
+            1,
         );
 
-        let approval_id: U64 = token.next_approval_id.into();
-        let is_new_approval = token
-            .approved_account_ids
-            .insert(account_id.clone(), approval_id)
-            .is_none();
-
         if self.use_storage_fees {
-            let storage_used = if is_new_approval {
-                bytes_for_approved_account_id(&account_id)
-            } else {
-                0
-            };
-
-            token.next_approval_id += 1;
-            self.tokens_by_id.insert(&token_id, &token);
-
             refund_deposit(storage_used);
         }
-        else{
-            token.next_approval_id += 1;
-            self.tokens_by_id.insert(&token_id, &token);
-        }
 
-        if let Some(msg) = msg {
+        approval_id.into()
+    }
 
-            // CUSTOM - add token_type to msg
-            let mut final_msg = msg;
-            let token_type = token.token_type;
-            if let Some(token_type) = token_type {
-                final_msg.insert_str(final_msg.len() - 1, &format!(",\"token_type\":\"{}\"", token_type));
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: AccountId,
+        approval_id: Option<U64>,
+    ) -> bool {
+        let token = self.tokens_by_id.get(&token_id).expect("Token not found");
+        if let Some(approval) = token.approved_account_ids.get(&approved_account_id) {
+            let expires_at = self
+                .approvals_expire_at
+                .get(&token_id)
+                .and_then(|approvals_expire_at| approvals_expire_at.get(&approved_account_id).copied());
+            if let Some(expires_at) = expires_at {
+                if env::block_timestamp() >= expires_at {
+                    return false;
+                }
             }
-
-            ext_non_fungible_approval_receiver::nft_on_approve(
-                token_id,
-                token.owner_id,
-                approval_id,
-                final_msg,
-                &account_id,
-                NO_DEPOSIT,
-                env::prepaid_gas() - GAS_FOR_NFT_APPROVE,
-            )
-                .as_return(); // Returning this promise
+            if let Some(approval_id) = approval_id {
+                approval_id == *approval
+            } else {
+                true
+            }
+        } else {
+            false
         }
     }
 
@@ -304,8 +333,16 @@ impl NonFungibleTokenCore for Contract {
             .remove(account_id.as_ref())
             .is_some()
         {
+            let mut approvals_expire_at = self.approvals_expire_at.get(&token_id).unwrap_or_default();
             if self.use_storage_fees {
-                refund_approved_account_ids_iter(predecessor_account_id, [account_id.into()].iter());
+                refund_approved_account_ids_iter(
+                    predecessor_account_id,
+                    [account_id.to_string()].iter(),
+                    &approvals_expire_at,
+                );
+            }
+            if approvals_expire_at.remove(account_id.as_ref()).is_some() {
+                self.internal_set_approvals_expire_at(&token_id, &approvals_expire_at);
             }
             self.tokens_by_id.insert(&token_id, &token);
         }
@@ -318,21 +355,41 @@ impl NonFungibleTokenCore for Contract {
         let predecessor_account_id = env::predecessor_account_id();
         assert_eq!(&predecessor_account_id, &token.owner_id);
         if !token.approved_account_ids.is_empty() {
+            let approvals_expire_at = self.approvals_expire_at.remove(&token_id).unwrap_or_default();
             if self.use_storage_fees {
-                refund_approved_account_ids(predecessor_account_id, &token.approved_account_ids);
+                refund_approved_account_ids(predecessor_account_id, &token.approved_account_ids, &approvals_expire_at);
             }
             token.approved_account_ids.clear();
             self.tokens_by_id.insert(&token_id, &token);
         }
     }
 
+    /// live tokens only, burns remove the metadata entry. A burned token_id can be minted again
+    /// when passed explicitly, the ids nft_mint assigns itself are never reused.
     fn nft_total_supply(&self) -> U64 {
         self.token_metadata_by_id.len().into()
     }
 
     fn nft_token(&self, token_id: TokenId) -> Option<JsonToken> {
         if let Some(token) = self.tokens_by_id.get(&token_id) {
-            let metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+            let mut metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+            // CUSTOM - relative media is resolved against the contract base_uri
+            if let Some(media) = &metadata.media {
+                if is_relative_uri(media) {
+                    if let Some(base_uri) = self.metadata.get().unwrap().base_uri {
+                        metadata.media = Some(join_uri(&base_uri, media));
+                    }
+                }
+            }
+            let transferable = !self.soulbound_tokens.contains(&token_id);
+            let creator_id = self.creator_by_id.get(&token_id);
+            let approvals_expire_at = self
+                .approvals_expire_at
+                .get(&token_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(account_id, expires_at)| (account_id, U64(expires_at)))
+                .collect();
             Some(JsonToken {
                 token_id,
                 owner_id: token.owner_id,
@@ -340,6 +397,9 @@ impl NonFungibleTokenCore for Contract {
                 royalty: token.royalty,
                 approved_account_ids: token.approved_account_ids,
                 token_type: token.token_type,
+                transferable,
+                creator_id,
+                approvals_expire_at,
             })
         } else {
             None
@@ -355,6 +415,7 @@ impl NonFungibleTokenResolver for Contract {
         owner_id: AccountId,
         receiver_id: AccountId,
         approved_account_ids: HashMap<AccountId, U64>,
+        approvals_expire_at: HashMap<AccountId, u64>,
         token_id: TokenId,
     ) -> bool {
         // Whether receiver wants to return token back to the sender, based on `nft_on_transfer`
@@ -365,7 +426,7 @@ impl NonFungibleTokenResolver for Contract {
                     // Token was successfully received.
 
                     if self.use_storage_fees {
-                        refund_approved_account_ids(owner_id, &approved_account_ids);
+                        refund_approved_account_ids(owner_id, &approved_account_ids, &approvals_expire_at);
                     }
                     return true;
                 }
@@ -376,7 +437,7 @@ impl NonFungibleTokenResolver for Contract {
             if token.owner_id != receiver_id {
                 // The token is not owner by the receiver anymore. Can't return it.
                 if self.use_storage_fees {
-                    refund_approved_account_ids(owner_id, &approved_account_ids);
+                    refund_approved_account_ids(owner_id, &approved_account_ids, &approvals_expire_at);
                 }
                 return true;
             }
@@ -384,7 +445,7 @@ impl NonFungibleTokenResolver for Contract {
         } else {
             // The token was burned and doesn't exist anymore.
             if self.use_storage_fees {
-                refund_approved_account_ids(owner_id, &approved_account_ids);
+                refund_approved_account_ids(owner_id, &approved_account_ids, &approvals_expire_at);
             }
             return true;
         };
@@ -395,11 +456,172 @@ impl NonFungibleTokenResolver for Contract {
         self.internal_add_token_to_owner(&owner_id, &token_id);
         token.owner_id = owner_id;
         if self.use_storage_fees {
-            refund_approved_account_ids(receiver_id, &token.approved_account_ids);
+            let receiver_approvals_expire_at = self.approvals_expire_at.get(&token_id).unwrap_or_default();
+            refund_approved_account_ids(receiver_id, &token.approved_account_ids, &receiver_approvals_expire_at);
         }
         token.approved_account_ids = approved_account_ids;
+        self.internal_set_approvals_expire_at(&token_id, &approvals_expire_at);
+        self.last_transfer_at.insert(&token_id, &env::block_timestamp());
         self.tokens_by_id.insert(&token_id, &token);
 
         false
     }
 }
+
+#[near_bindgen]
+impl Contract {
+    /// CUSTOM - emergency revoke of every approval on a token by the contract owner, storage goes back to the token owner
+    #[payable]
+    pub fn admin_clear_approvals(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        self.assert_owner();
+        let mut token = self.tokens_by_id.get(&token_id).expect("Token not found");
+        if !token.approved_account_ids.is_empty() {
+            let approvals_expire_at = self.approvals_expire_at.remove(&token_id).unwrap_or_default();
+            if self.use_storage_fees {
+                refund_approved_account_ids(token.owner_id.clone(), &token.approved_account_ids, &approvals_expire_at);
+            }
+            token.approved_account_ids.clear();
+            self.tokens_by_id.insert(&token_id, &token);
+        }
+    }
+
+    /// CUSTOM - approves every token for account_id, reverts if any of them isn't owned by the predecessor
+    #[payable]
+    pub fn nft_approve_batch(
+        &mut self,
+        token_ids: Vec<TokenId>,
+        account_id: ValidAccountId,
+        msg: Option<String>,
+        expires_at: Option<U64>,
+    ) -> Vec<u64> {
+        if self.use_storage_fees {
+            assert_at_least_one_yocto();
+        } else {
+            assert_one_yocto();
+        }
+        assert!(!token_ids.is_empty(), "No token ids given");
+
+        let account_id: AccountId = account_id.into();
+        let approvals_count = token_ids.len() as u64;
+        let mut total_storage_used = 0;
+        let approval_ids = token_ids
+            .iter()
+            .map(|token_id| {
+                let (approval_id, storage_used) = self.internal_approve(
+                    token_id,
+                    &account_id,
+                    expires_at.map(u64::from),
+                    msg.clone(),
+                    approvals_count,
+                );
+                total_storage_used += storage_used;
+                approval_id.into()
+            })
+            .collect();
+
+        if self.use_storage_fees {
+            refund_deposit(total_storage_used);
+        }
+
+        approval_ids
+    }
+}
+
+impl Contract {
+    /// Approves `account_id` for the token and calls `nft_on_approve` when a `msg` is given, each
+    /// approval of a batch of `approvals_count` gets an equal share of the prepaid gas.
+    /// Returns the approval id and the storage in bytes added by the approval.
+    pub(crate) fn internal_approve(
+        &mut self,
+        token_id: &TokenId,
+        account_id: &AccountId,
+        expires_at: Option<u64>,
+        msg: Option<String>,
+        approvals_count: u64,
+    ) -> (U64, u64) {
+        let mut token = self.tokens_by_id.get(token_id).expect("Token not found");
+
+        assert_eq!(
+            &env::predecessor_account_id(),
+            &token.owner_id,
+            "Predecessor must be the token owner."
+        );
+
+        assert!(
+            token.approved_account_ids.contains_key(account_id)
+                || token.approved_account_ids.len() < MAX_APPROVALS_PER_TOKEN,
+            "Too many approvals, revoke one first"
+        );
+
+        let approval_id: U64 = token.next_approval_id.into();
+        let is_new_approval = token
+            .approved_account_ids
+            .insert(account_id.clone(), approval_id)
+            .is_none();
+
+        // CUSTOM - re-approving replaces the previous expiry
+        let mut approvals_expire_at = self.approvals_expire_at.get(token_id).unwrap_or_default();
+        let had_expiry = if let Some(expires_at) = expires_at {
+            assert!(expires_at > env::block_timestamp(), "Approval expiry must be in the future");
+            approvals_expire_at.insert(account_id.clone(), expires_at).is_some()
+        } else {
+            approvals_expire_at.remove(account_id).is_some()
+        };
+        if expires_at.is_some() || had_expiry {
+            self.internal_set_approvals_expire_at(token_id, &approvals_expire_at);
+        }
+
+        token.next_approval_id += 1;
+        self.tokens_by_id.insert(token_id, &token);
+
+        // approvals pay what revoke refunds: the account once, the expiry only while there is one
+        let mut storage_used = 0;
+        if is_new_approval {
+            storage_used += bytes_for_approved_account_id(account_id);
+        }
+        if expires_at.is_some() && !had_expiry {
+            storage_used += bytes_for_approval_expiry(account_id);
+        }
+        if expires_at.is_none() && had_expiry && self.use_storage_fees {
+            refund_released_storage(token.owner_id.clone(), bytes_for_approval_expiry(account_id));
+        }
+
+        if let Some(msg) = msg {
+            let gas_per_approval = env::prepaid_gas() / approvals_count;
+            assert!(gas_per_approval > GAS_FOR_NFT_APPROVE, "Not enough gas to call nft_on_approve for every approval");
+
+            // CUSTOM - add token_type to msg
+            let mut final_msg = msg;
+            let token_type = token.token_type;
+            if let Some(token_type) = token_type {
+                final_msg.insert_str(final_msg.len() - 1, &format!(",\"token_type\":\"{}\"", token_type));
+            }
+
+            ext_non_fungible_approval_receiver::nft_on_approve(
+                token_id.clone(),
+                token.owner_id,
+                approval_id,
+                final_msg,
+                account_id,
+                NO_DEPOSIT,
+                gas_per_approval - GAS_FOR_NFT_APPROVE,
+            );
+        }
+
+        (approval_id, storage_used)
+    }
+
+    /// the entry is dropped with the last expiry so tokens without expiring approvals store nothing
+    pub(crate) fn internal_set_approvals_expire_at(
+        &mut self,
+        token_id: &TokenId,
+        approvals_expire_at: &HashMap<AccountId, u64>,
+    ) {
+        if approvals_expire_at.is_empty() {
+            self.approvals_expire_at.remove(token_id);
+        } else {
+            self.approvals_expire_at.insert(token_id, approvals_expire_at);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use near_sdk::test_utils::{accounts, get_logs};
+
+use super::*;
+
+fn supply_caps(supply_caps: &[(&str, u64)]) -> TypeSupplyCaps {
+    supply_caps
+        .iter()
+        .map(|(token_type, supply_cap)| (token_type.to_string(), U64(*supply_cap)))
+        .collect()
+}
+
+#[test]
+fn add_new_token_type() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.add_token_types(supply_caps(&[("B", 5)]), Some(true));
+
+    assert_eq!(contract.get_supply_caps().get("B"), Some(&U64(5)));
+    assert_eq!(contract.get_supply_caps().get("A"), Some(&U64(10)));
+}
+
+#[test]
+#[should_panic(expected = "Token type already exists, use increase_supply_cap")]
+fn add_existing_token_type_fails() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    set_caller(owner(), 0);
+
+    contract.add_token_types(supply_caps(&[("A", 1)]), Some(true));
+}
+
+#[test]
+fn increase_supply_cap_of_existing_type() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.increase_supply_cap("A".to_string(), U64(20));
+
+    assert_eq!(contract.get_supply_caps().get("A"), Some(&U64(20)));
+}
+
+#[test]
+#[should_panic(expected = "New supply cap must be greater than the current one")]
+fn increase_supply_cap_below_current_fails() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+
+    contract.increase_supply_cap("A".to_string(), U64(5));
+}
+
+#[test]
+fn unlock_all_token_types_allows_minting() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string(), "B".to_string()]);
+
+    contract.unlock_all_token_types();
+
+    let logs = get_logs();
+    assert!(logs[0].starts_with("EVENT_JSON:"));
+    let event: serde_json::Value = serde_json::from_str(logs[0].trim_start_matches("EVENT_JSON:")).unwrap();
+    assert_eq!(event["standard"], "pluminite");
+    assert_eq!(event["event"], "token_types_unlocked");
+    assert_eq!(event["data"][0]["token_types"], serde_json::json!(["A", "B"]));
+    assert!(contract.get_token_types_locked().is_empty());
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    mint(&mut contract, accounts(1), "2", Some("B"));
+}
+
+#[test]
+fn rebuild_tokens_per_type_repairs_type_set() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    mint(&mut contract, accounts(1), "2", Some("A"));
+    mint(&mut contract, accounts(1), "3", None);
+    contract.internal_remove_token_from_type(&"A".to_string(), &"1".to_string());
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(1));
+
+    set_caller(owner(), 0);
+    contract.rebuild_tokens_per_type(U64(0), 10);
+    contract.rebuild_tokens_per_type(U64(0), 10);
+
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(2));
+}
+
+fn numbered_types(from: usize, to: usize) -> TypeSupplyCaps {
+    (from..to).map(|i| (format!("T{}", i), U64(1))).collect()
+}
+
+#[test]
+fn add_token_types_up_to_max() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+
+    contract.add_token_types(numbered_types(1, MAX_TOKEN_TYPES), Some(true));
+
+    assert_eq!(contract.get_token_type_count(), MAX_TOKEN_TYPES as u64);
+}
+
+#[test]
+#[should_panic(expected = "Cannot add more than 100 token types")]
+fn add_token_types_over_max_fails() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.add_token_types(numbered_types(1, MAX_TOKEN_TYPES), Some(true));
+
+    contract.add_token_types(numbered_types(MAX_TOKEN_TYPES, MAX_TOKEN_TYPES + 1), Some(true));
+}
+
+#[test]
+fn storage_cost_measured_with_64_char_owner_id() {
+    let owner_id: ValidAccountId = "a".repeat(64).try_into().unwrap();
+    set_caller(owner_id.clone(), 0);
+
+    let contract = Contract::new(owner_id, sample_nft_metadata(), HashMap::new(), false, 0, Some(true));
+
+    assert!(contract.get_extra_storage_in_bytes_per_token().0 > 0);
+}
+
+#[test]
+fn type_manager_manages_their_type() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    set_caller(owner(), 0);
+    contract.set_type_manager("A".to_string(), Some(accounts(1)));
+
+    set_caller(accounts(1), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+    assert_eq!(contract.get_token_types_locked(), vec!["A".to_string()]);
+    contract.increase_supply_cap("A".to_string(), U64(20));
+    assert_eq!(contract.get_supply_caps().get("A"), Some(&U64(20)));
+    contract.unlock_token_types(vec!["A".to_string()]);
+    assert!(contract.get_token_types_locked().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Owner's or type manager's method")]
+fn type_manager_cannot_manage_other_types() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    set_caller(owner(), 0);
+    contract.set_type_manager("A".to_string(), Some(accounts(1)));
+
+    set_caller(accounts(1), 0);
+    contract.lock_token_types(vec!["B".to_string()]);
+}
+
+#[test]
+#[should_panic(expected = "Owner's or type manager's method")]
+fn removed_type_manager_cannot_manage_type() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.set_type_manager("A".to_string(), Some(accounts(1)));
+    contract.set_type_manager("A".to_string(), None);
+
+    set_caller(accounts(1), 0);
+    contract.increase_supply_cap("A".to_string(), U64(20));
+}
+
+#[test]
+#[should_panic(expected = "Supply cap must be greater than zero")]
+fn new_with_zero_supply_cap_fails() {
+    new_contract(&[("A", 0)]);
+}
+
+#[test]
+#[should_panic(expected = "Token type name cannot be empty")]
+fn new_with_empty_token_type_fails() {
+    new_contract(&[("", 10)]);
+}
+
+#[test]
+fn propose_then_confirm_contract_royalty() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+
+    contract.propose_contract_royalty(300);
+    assert_eq!(contract.get_pending_contract_royalty(), Some(300));
+    assert_eq!(contract.get_contract_royalty(), 0);
+    contract.confirm_contract_royalty();
+
+    assert_eq!(contract.get_contract_royalty(), 300);
+    assert_eq!(contract.get_pending_contract_royalty(), None);
+}
+
+#[test]
+#[should_panic(expected = "No pending contract royalty")]
+fn confirm_contract_royalty_without_proposal_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+
+    contract.confirm_contract_royalty();
+}
+
+#[test]
+fn remeasure_storage_cost_keeps_owner_tokens() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    let measured = contract.get_extra_storage_in_bytes_per_token();
+
+    set_caller(owner(), 0);
+    let remeasured = contract.remeasure_storage_cost();
+
+    assert!(remeasured.0 > 0);
+    assert_eq!(remeasured, measured);
+    assert_eq!(contract.nft_supply_for_owner(accounts(1).into()), U128(1));
+}
+
+#[test]
+#[should_panic(expected = "Owner's method")]
+fn remeasure_storage_cost_by_non_owner_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+
+    contract.remeasure_storage_cost();
+}
+
+#[test]
+fn nft_set_metadata_until_frozen() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    let mut metadata = sample_nft_metadata();
+    metadata.name = "Pluminite Gallery".to_string();
+    contract.nft_set_metadata(metadata);
+    assert_eq!(contract.nft_metadata().name, "Pluminite Gallery");
+    assert!(!contract.is_contract_metadata_frozen());
+
+    contract.freeze_contract_metadata();
+
+    assert!(contract.is_contract_metadata_frozen());
+}
+
+#[test]
+#[should_panic(expected = "Contract metadata is frozen")]
+fn nft_set_metadata_after_freeze_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    contract.freeze_contract_metadata();
+
+    contract.nft_set_metadata(sample_nft_metadata());
+}
+
+#[test]
+fn rename_token_type_moves_tokens_and_settings() {
+    let mut contract = new_contract(&[("A", 5), ("B", 5)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    mint(&mut contract, accounts(1), "2", Some("A"));
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    contract.rename_token_type("A".to_string(), "C".to_string());
+
+    assert_eq!(contract.get_supply_caps(), supply_caps(&[("B", 5), ("C", 5)]));
+    assert_eq!(contract.get_remaining_supply("C".to_string()), Some(U64(3)));
+    assert_eq!(contract.nft_supply_for_type("C".to_string()), U64(2));
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(0));
+    assert_eq!(contract.nft_token_ids_for_type("C".to_string(), U64(0), U64(10)), vec!["1", "2"]);
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().token_type, Some("C".to_string()));
+    assert_eq!(contract.get_token_types_locked(), vec!["C".to_string()]);
+    assert!(contract.is_token_locked("2".to_string()));
+    assert!(!contract.token_type_exists("A".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Token type already exists")]
+fn rename_token_type_to_existing_type_fails() {
+    let mut contract = new_contract(&[("A", 5), ("B", 5)]);
+    set_caller(owner(), 0);
+
+    contract.rename_token_type("A".to_string(), "B".to_string());
+}
+
+#[test]
+fn extra_storage_per_token_covers_a_new_holder() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    mint(&mut contract, accounts(1), "2", None);
+
+    set_caller(accounts(1), 1);
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_transfer(accounts(2), "1".to_string(), None, None);
+
+    assert_eq!(contract.holders.len(), 2);
+    assert!(env::storage_usage() - initial_storage_usage <= contract.get_extra_storage_in_bytes_per_token().0);
+}
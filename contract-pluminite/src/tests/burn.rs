@@ -0,0 +1,245 @@
+use near_sdk::test_utils::{accounts, get_logs};
+
+use super::*;
+
+fn burn_events() -> Vec<String> {
+    get_logs().into_iter().filter(|log| log.contains("\"event\":\"nft_burn\"")).collect()
+}
+
+/// starts nft_burn_call of token_id by accounts(1) and returns what nft_resolve_burn is called with
+fn burn_call(contract: &mut Contract, token_id: &str) -> (Token, TokenMetadata, U64) {
+    let token = contract.tokens_by_id.get(&token_id.to_string()).unwrap();
+    let metadata = contract.token_metadata_by_id.get(&token_id.to_string()).unwrap();
+    set_caller(accounts(1), 1);
+    let initial_storage_usage = env::storage_usage();
+    contract.nft_burn_call(token_id.to_string(), accounts(2), "{}".to_string());
+    assert!(burn_events().is_empty());
+    (token, metadata, U64(initial_storage_usage - env::storage_usage()))
+}
+
+fn resolve_burn(contract: &mut Contract, token_id: &str, call: (Token, TokenMetadata, U64), promise_result: PromiseResult) -> bool {
+    let (token, metadata, storage_released) = call;
+    set_callback_result(promise_result);
+    contract.nft_resolve_burn(token_id.to_string(), token, metadata, storage_released)
+}
+
+#[test]
+fn nft_burn_call_redeemed() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    set_caller(owner(), 0);
+    contract.hide_token("1".to_string());
+
+    let call = burn_call(&mut contract, "1");
+    assert!(!contract.nft_token_exists("1".to_string()));
+
+    assert!(resolve_burn(&mut contract, "1", call, PromiseResult::Successful(vec![])));
+    assert_eq!(burn_events().len(), 1);
+    assert!(!contract.nft_token_exists("1".to_string()));
+    assert!(contract.get_hidden_tokens().is_empty());
+    assert_eq!(contract.nft_token_creator("1".to_string()), None);
+}
+
+#[test]
+fn nft_burn_call_restored_on_failure() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    set_caller(owner(), 0);
+    contract.hide_token("1".to_string());
+
+    let call = burn_call(&mut contract, "1");
+
+    assert!(!resolve_burn(&mut contract, "1", call, PromiseResult::Failed));
+    assert!(burn_events().is_empty());
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().owner_id, accounts(1).to_string());
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(1));
+    assert_eq!(contract.nft_supply_for_owner(accounts(1).into()), U128(1));
+    assert_eq!(contract.get_hidden_tokens(), vec!["1".to_string()]);
+    assert_eq!(contract.nft_token_creator("1".to_string()), Some(accounts(1).into()));
+}
+
+#[test]
+fn nft_burn_call_redeemed_releases_unique_media() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.set_unique_media_for_type("A".to_string(), true);
+    let mut metadata = sample_token_metadata("1");
+    metadata.media = Some("ipfs://one".to_string());
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), metadata, None, None, Some("A".to_string()), None, None);
+
+    let call = burn_call(&mut contract, "1");
+    resolve_burn(&mut contract, "1", call, PromiseResult::Successful(vec![]));
+
+    let mut metadata = sample_token_metadata("2");
+    metadata.media = Some("ipfs://one".to_string());
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("2".to_string()), metadata, None, None, Some("A".to_string()), None, None);
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(1));
+}
+
+fn burn_event_data(event: &str) -> serde_json::Value {
+    serde_json::from_str::<serde_json::Value>(event.trim_start_matches("EVENT_JSON:")).unwrap()["data"].clone()
+}
+
+#[test]
+fn nft_burn_batch_emits_one_event() {
+    let mut contract = new_contract(&[]);
+    for token_id in ["1", "2", "3"].iter() {
+        mint(&mut contract, accounts(1), token_id, None);
+    }
+
+    set_caller(accounts(1), 1);
+    contract.nft_burn_batch(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+
+    let events = burn_events();
+    assert_eq!(events.len(), 1);
+    let data = burn_event_data(&events[0]);
+    assert_eq!(data.as_array().unwrap().len(), 1);
+    assert_eq!(data[0]["owner_id"], accounts(1).to_string());
+    assert_eq!(data[0]["token_ids"], serde_json::json!(["1", "2", "3"]));
+}
+
+#[test]
+fn burn_event_carries_standard_and_version() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+
+    set_caller(accounts(1), 1);
+    contract.nft_burn("1".to_string());
+
+    let events = burn_events();
+    assert_eq!(events.len(), 1);
+    let event: serde_json::Value = serde_json::from_str(events[0].trim_start_matches("EVENT_JSON:")).unwrap();
+    assert_eq!(event["standard"], EVENT_STANDARD);
+    assert_eq!(event["version"], EVENT_VERSION);
+    assert_eq!(event["event"], "nft_burn");
+}
+
+#[test]
+fn nft_burn_batch_burns_every_token() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+    mint(&mut contract, accounts(1), "2", None);
+
+    set_caller(accounts(1), 1);
+    contract.nft_burn_batch(vec!["1".to_string(), "2".to_string()]);
+
+    assert_eq!(contract.nft_total_supply(), U64(0));
+    assert!(transferred_to(&accounts(1)) > 0);
+}
+
+#[test]
+#[should_panic(expected = "Predecessor must be the token owner.")]
+fn nft_burn_batch_with_non_owned_token_fails() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    mint(&mut contract, accounts(2), "2", None);
+
+    set_caller(accounts(1), 1);
+    contract.nft_burn_batch(vec!["1".to_string(), "2".to_string()]);
+}
+
+#[test]
+fn nft_total_supply_counts_live_tokens() {
+    let mut contract = new_contract(&[]);
+    for token_id in ["1", "2", "3"].iter() {
+        mint(&mut contract, accounts(1), token_id, None);
+    }
+
+    set_caller(accounts(1), 1);
+    contract.nft_burn("2".to_string());
+    assert_eq!(contract.nft_total_supply(), U64(2));
+    contract.nft_burn_batch(vec!["1".to_string(), "3".to_string()]);
+
+    assert_eq!(contract.nft_total_supply(), U64(0));
+}
+
+#[test]
+fn burned_token_id_minted_again_explicitly() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(None, sample_token_metadata("1"), None, None, None, None, None);
+    set_caller(accounts(1), 1);
+    contract.nft_burn("1".to_string());
+
+    mint(&mut contract, accounts(2), "1", None);
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(None, sample_token_metadata("auto"), None, None, None, None, None);
+
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().owner_id, accounts(2).to_string());
+    assert!(contract.nft_token_exists("3".to_string()));
+    assert_eq!(contract.nft_total_supply(), U64(2));
+}
+
+fn burn_at(contract: &mut Contract, token_id: &str, block_timestamp: u64) {
+    testing_env!(get_context(accounts(1), 1).block_timestamp(block_timestamp).build());
+    contract.nft_burn(token_id.to_string());
+}
+
+fn claim_burn_refund_at(contract: &mut Contract, block_timestamp: u64) -> U128 {
+    testing_env!(get_context(accounts(1), 0).block_timestamp(block_timestamp).build());
+    contract.claim_burn_refund()
+}
+
+fn new_contract_with_burn_refund_delay(burn_refund_delay_ns: Option<u64>) -> Contract {
+    let mut contract = new_contract_with(&[], true, 0);
+    set_caller(owner(), 0);
+    contract.set_burn_refund_delay(burn_refund_delay_ns.map(U64));
+    mint(&mut contract, accounts(1), "1", None);
+    contract
+}
+
+#[test]
+fn burn_refund_without_delay_is_immediate() {
+    for burn_refund_delay_ns in [None, Some(0)].iter() {
+        let mut contract = new_contract_with_burn_refund_delay(*burn_refund_delay_ns);
+
+        burn_at(&mut contract, "1", 100);
+
+        assert!(transferred_to(&accounts(1)) > 0);
+        assert_eq!(contract.get_pending_burn_refund(accounts(1).into()), None);
+    }
+}
+
+#[test]
+fn burn_refund_claimable_after_delay() {
+    let mut contract = new_contract_with_burn_refund_delay(Some(1_000));
+
+    burn_at(&mut contract, "1", 100);
+    assert_eq!(transferred_to(&accounts(1)), 0);
+    let (amount, claimable_at) = contract.get_pending_burn_refund(accounts(1).into()).unwrap();
+    assert!(amount.0 > 0);
+    assert_eq!(claimable_at, U64(1_100));
+
+    assert_eq!(claim_burn_refund_at(&mut contract, 1_100), amount);
+    assert_eq!(transferred_to(&accounts(1)), amount.0);
+    assert_eq!(contract.get_pending_burn_refund(accounts(1).into()), None);
+}
+
+#[test]
+#[should_panic(expected = "Burn refund is not claimable yet")]
+fn claim_burn_refund_before_delay_fails() {
+    let mut contract = new_contract_with_burn_refund_delay(Some(1_000));
+    burn_at(&mut contract, "1", 100);
+
+    claim_burn_refund_at(&mut contract, 1_099);
+}
+
+#[test]
+#[should_panic(expected = "No pending burn refund")]
+fn claim_burn_refund_without_pending_refund_fails() {
+    let mut contract = new_contract_with_burn_refund_delay(Some(1_000));
+
+    claim_burn_refund_at(&mut contract, 100);
+}
+
+#[test]
+fn burn_refund_delay_near_u64_max_does_not_overflow() {
+    let mut contract = new_contract_with_burn_refund_delay(Some(u64::MAX));
+
+    burn_at(&mut contract, "1", 100);
+
+    let (_, claimable_at) = contract.get_pending_burn_refund(accounts(1).into()).unwrap();
+    assert_eq!(claimable_at, U64(u64::MAX));
+}
@@ -0,0 +1,214 @@
+use near_sdk::test_utils::accounts;
+
+use super::*;
+
+#[test]
+fn free_mint_available_below_free_mints() {
+    let contract = new_contract_with(&[], false, 2);
+
+    assert!(contract.is_free_mint_available(accounts(1).into(), None));
+}
+
+#[test]
+fn free_mints_disabled_overrides_free_mints() {
+    let mut contract = new_contract_with(&[], false, 2);
+    set_caller(owner(), 0);
+    contract.set_free_mints_enabled(false);
+
+    assert!(!contract.get_free_mints_enabled());
+    assert!(!contract.is_free_mint_available(accounts(1).into(), None));
+}
+
+#[test]
+fn free_mints_enabled_again() {
+    let mut contract = new_contract_with(&[], false, 2);
+    set_caller(owner(), 0);
+    contract.set_free_mints_enabled(false);
+    contract.set_free_mints_enabled(true);
+
+    assert!(contract.is_free_mint_available(accounts(1).into(), None));
+}
+
+#[test]
+#[should_panic(expected = "Owner's method")]
+fn set_free_mints_enabled_by_non_owner_fails() {
+    let mut contract = new_contract_with(&[], false, 2);
+    set_caller(accounts(1), 0);
+
+    contract.set_free_mints_enabled(false);
+}
+
+#[test]
+fn blocklisted_account_loses_free_mints() {
+    let mut contract = new_contract_with(&[], false, 2);
+    set_caller(owner(), 0);
+    contract.add_to_free_mint_blocklist(accounts(1));
+
+    assert!(!contract.is_free_mint_available(accounts(1).into(), None));
+    assert!(contract.is_free_mint_available(accounts(2).into(), None));
+    assert_eq!(contract.get_free_mint_blocklist(), vec![accounts(1).to_string()]);
+}
+
+#[test]
+fn account_removed_from_blocklist_regains_free_mints() {
+    let mut contract = new_contract_with(&[], false, 2);
+    set_caller(owner(), 0);
+    contract.add_to_free_mint_blocklist(accounts(1));
+    contract.remove_from_free_mint_blocklist(accounts(1));
+
+    assert!(contract.is_free_mint_available(accounts(1).into(), None));
+}
+
+fn free_mint_at(contract: &mut Contract, token_id: &str, block_timestamp: u64) {
+    testing_env!(get_context(accounts(1), MINT_DEPOSIT).block_timestamp(block_timestamp).build());
+    assert!(contract.is_free_mint_available(accounts(1).into(), None));
+    contract.nft_mint(Some(token_id.to_string()), sample_token_metadata(token_id), None, None, None, None, None);
+}
+
+#[test]
+fn free_mint_after_cooldown() {
+    let mut contract = new_contract_with(&[], false, 3);
+    set_caller(owner(), 0);
+    contract.set_free_mint_cooldown(Some(U64(100)));
+    free_mint_at(&mut contract, "1", 0);
+
+    free_mint_at(&mut contract, "2", 100);
+
+    assert_eq!(contract.get_free_mints_used(accounts(1).into()), 2);
+}
+
+fn mint_cost_at(contract: &mut Contract, token_id: &str, block_timestamp: u64) -> Balance {
+    testing_env!(get_context(accounts(1), MINT_DEPOSIT).block_timestamp(block_timestamp).build());
+    contract.nft_mint(Some(token_id.to_string()), sample_token_metadata(token_id), None, None, None, None, None);
+    MINT_DEPOSIT - transferred_to(&accounts(1))
+}
+
+#[test]
+fn mint_within_cooldown_pays_for_storage() {
+    let mut contract = new_contract_with(&[], false, 3);
+    set_caller(owner(), 0);
+    contract.set_free_mint_cooldown(Some(U64(100)));
+    let free_mint_cost = mint_cost_at(&mut contract, "1", 0);
+
+    testing_env!(get_context(accounts(1), 0).block_timestamp(50).build());
+    assert!(!contract.is_free_mint_available(accounts(1).into(), None));
+    let paid_mint_cost = mint_cost_at(&mut contract, "2", 50);
+
+    assert!(free_mint_cost < paid_mint_cost);
+    // the paid mint doesn't restart the cooldown
+    testing_env!(get_context(accounts(1), 0).block_timestamp(100).build());
+    assert!(contract.is_free_mint_available(accounts(1).into(), None));
+}
+
+#[test]
+#[should_panic(expected = "Must attach")]
+fn mint_within_cooldown_without_deposit_fails() {
+    let mut contract = new_contract_with(&[], false, 3);
+    set_caller(owner(), 0);
+    contract.set_free_mint_cooldown(Some(U64(100)));
+    free_mint_at(&mut contract, "1", 0);
+
+    testing_env!(get_context(accounts(1), 0).block_timestamp(99).build());
+    contract.nft_mint(Some("2".to_string()), sample_token_metadata("2"), None, None, None, None, None);
+}
+
+#[test]
+fn free_mint_cooldown_near_u64_max_does_not_overflow() {
+    let mut contract = new_contract_with(&[], false, 3);
+    set_caller(owner(), 0);
+    contract.set_free_mint_cooldown(Some(U64(u64::MAX)));
+    free_mint_at(&mut contract, "1", 10);
+
+    testing_env!(get_context(accounts(1), 0).block_timestamp(u64::MAX - 1).build());
+    assert!(!contract.is_free_mint_available(accounts(1).into(), None));
+}
+
+#[test]
+fn free_mints_for_type_override_global() {
+    let mut contract = new_contract_with(&[("A", 10), ("B", 10)], false, 1);
+    set_caller(owner(), 0);
+    contract.set_free_mints_for_type("A".to_string(), Some(0));
+
+    assert_eq!(contract.get_free_mints_for_type("A".to_string()), 0);
+    assert!(!contract.is_free_mint_available(accounts(1).into(), Some("A".to_string())));
+    assert_eq!(contract.get_free_mints_for_type("B".to_string()), 1);
+    assert!(contract.is_free_mint_available(accounts(1).into(), Some("B".to_string())));
+}
+
+#[test]
+fn free_mints_for_type_fall_back_to_global_when_unset() {
+    let mut contract = new_contract_with(&[("A", 10)], false, 1);
+    set_caller(owner(), 0);
+    contract.set_free_mints_for_type("A".to_string(), Some(5));
+    contract.set_free_mints_for_type("A".to_string(), None);
+
+    assert_eq!(contract.get_free_mints_for_type("A".to_string()), 1);
+}
+
+#[test]
+#[should_panic(expected = "Must attach")]
+fn free_mint_without_deposit_fails() {
+    let mut contract = new_contract_with(&[], false, 1);
+    set_caller(accounts(1), 0);
+    assert!(contract.is_free_mint_available(accounts(1).into(), None));
+
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), None, None, None, None, None);
+}
+
+#[test]
+fn free_mint_costs_less_than_paid_mint() {
+    let mut contract = new_contract_with(&[], false, 1);
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), None, None, None, None, None);
+    let free_mint_cost = MINT_DEPOSIT - transferred_to(&accounts(1));
+
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("2".to_string()), sample_token_metadata("2"), None, None, None, None, None);
+    let paid_mint_cost = MINT_DEPOSIT - transferred_to(&accounts(1));
+
+    assert!(free_mint_cost > 0);
+    assert!(free_mint_cost < paid_mint_cost);
+}
+
+#[test]
+fn free_mint_mode_across_flags() {
+    assert!(new_contract_with(&[], false, 1).is_free_mint_mode());
+    assert!(!new_contract_with(&[], true, 1).is_free_mint_mode());
+    assert!(!new_contract_with(&[], false, 0).is_free_mint_mode());
+
+    let mut contract = new_contract_with(&[], false, 1);
+    set_caller(owner(), 0);
+    contract.set_free_mints_enabled(false);
+    assert!(!contract.is_free_mint_mode());
+}
+
+#[test]
+fn global_free_mint_budget_drained() {
+    let mut contract = new_contract_with(&[], false, 5);
+    set_caller(owner(), 0);
+    contract.set_global_free_mint_budget(Some(2));
+
+    free_mint_at(&mut contract, "1", 0);
+    assert_eq!(contract.get_global_free_mints_remaining(), Some(1));
+    free_mint_at(&mut contract, "2", 0);
+
+    assert_eq!(contract.get_global_free_mints_remaining(), Some(0));
+    assert!(!contract.is_free_mint_available(accounts(1).into(), None));
+    // the next mint pays for its storage instead
+    mint(&mut contract, accounts(1), "3", None);
+    assert_eq!(contract.get_global_free_mints_remaining(), Some(0));
+}
+
+#[test]
+fn free_mints_used_below_and_above_limit() {
+    let mut contract = new_contract_with(&[], false, 2);
+    free_mint_at(&mut contract, "1", 0);
+    assert_eq!(contract.get_free_mints_used(accounts(1).into()), 1);
+
+    free_mint_at(&mut contract, "2", 0);
+    mint(&mut contract, accounts(1), "3", None);
+
+    assert_eq!(contract.get_tokens_created(accounts(1).into()), 3);
+    assert_eq!(contract.get_free_mints_used(accounts(1).into()), 2);
+    assert_eq!(contract.get_free_mints_used(accounts(2).into()), 0);
+}
@@ -0,0 +1,96 @@
+use near_sdk::test_utils::accounts;
+
+use super::*;
+
+/// state of a contract deployed before free_mints and version were added
+#[derive(BorshSerialize)]
+struct ContractV0 {
+    tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    tokens_per_creator: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    tokens_by_id: LookupMap<TokenId, Token>,
+    token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>,
+    owner_id: AccountId,
+    extra_storage_in_bytes_per_token: StorageUsage,
+    metadata: LazyOption<NFTMetadata>,
+    supply_cap_by_type: TypeSupplyCaps,
+    tokens_per_type: LookupMap<TokenType, UnorderedSet<TokenId>>,
+    token_types_locked: UnorderedSet<TokenType>,
+    contract_royalty: u32,
+    profiles: LookupMap<AccountId, Profile>,
+    use_storage_fees: bool,
+}
+
+/// ContractV0 followed by the fields read by migrate_state_2
+#[derive(BorshSerialize)]
+struct ContractV1 {
+    v0: ContractV0,
+    free_mints: u64,
+    version: u16,
+}
+
+fn contract_v0() -> ContractV0 {
+    ContractV0 {
+        tokens_per_owner: LookupMap::new(StorageKey::TokensPerOwner.try_to_vec().unwrap()),
+        tokens_per_creator: LookupMap::new(StorageKey::TokensPerCreator.try_to_vec().unwrap()),
+        tokens_by_id: LookupMap::new(StorageKey::TokensById.try_to_vec().unwrap()),
+        token_metadata_by_id: UnorderedMap::new(StorageKey::TokenMetadataById.try_to_vec().unwrap()),
+        owner_id: owner().into(),
+        extra_storage_in_bytes_per_token: 0,
+        metadata: LazyOption::new(StorageKey::NftMetadata.try_to_vec().unwrap(), Some(&sample_nft_metadata())),
+        supply_cap_by_type: HashMap::new(),
+        tokens_per_type: LookupMap::new(StorageKey::TokensPerType.try_to_vec().unwrap()),
+        token_types_locked: UnorderedSet::new(StorageKey::TokenTypesLocked.try_to_vec().unwrap()),
+        contract_royalty: 0,
+        profiles: LookupMap::new(StorageKey::Profiles.try_to_vec().unwrap()),
+        use_storage_fees: false,
+    }
+}
+
+/// migrations are called by the contract account on itself
+fn set_migration_caller(block_timestamp: u64) {
+    testing_env!(get_context(contract_account(), 0).block_timestamp(block_timestamp).build());
+}
+
+#[test]
+fn migrate_state_1_appends_migration_log() {
+    set_migration_caller(0);
+    env::state_write(&contract_v0());
+    set_migration_caller(1_000);
+
+    let contract = Contract::migrate_state_1();
+
+    assert_eq!(contract.get_version(), 1);
+    assert_eq!(contract.get_migration_log(), vec![(1, U64(1_000))]);
+}
+
+#[test]
+fn migrate_state_2_appends_migration_log() {
+    set_migration_caller(0);
+    env::state_write(&ContractV1 { v0: contract_v0(), free_mints: 3, version: 1 });
+    set_migration_caller(2_000);
+
+    let contract = Contract::migrate_state_2();
+
+    assert_eq!(contract.get_version(), 2);
+    assert_eq!(contract.get_free_mints(), 3);
+    assert_eq!(contract.get_migration_log(), vec![(2, U64(2_000))]);
+}
+
+#[test]
+#[should_panic(expected = "State is already migrated")]
+fn migrate_state_2_twice_fails() {
+    set_migration_caller(0);
+    env::state_write(&ContractV1 { v0: contract_v0(), free_mints: 3, version: 2 });
+
+    Contract::migrate_state_2();
+}
+
+#[test]
+#[should_panic(expected = "Private function")]
+fn migrate_state_2_by_other_account_fails() {
+    set_migration_caller(0);
+    env::state_write(&ContractV1 { v0: contract_v0(), free_mints: 3, version: 1 });
+    set_caller(accounts(1), 0);
+
+    Contract::migrate_state_2();
+}
@@ -0,0 +1,438 @@
+use near_sdk::test_utils::accounts;
+
+use super::*;
+
+fn mint_media(contract: &mut Contract, token_id: &str, token_type: &str, media: &str) {
+    let mut metadata = sample_token_metadata(token_id);
+    metadata.media = Some(media.to_string());
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some(token_id.to_string()), metadata, None, None, Some(token_type.to_string()), None, None);
+}
+
+#[test]
+fn mint_up_to_global_supply_cap() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    set_caller(owner(), 0);
+    contract.set_global_supply_cap(Some(2));
+
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    mint(&mut contract, accounts(1), "2", Some("B"));
+
+    assert_eq!(contract.nft_total_supply(), U64(2));
+}
+
+#[test]
+#[should_panic(expected = "Cannot mint anymore tokens.")]
+fn mint_at_global_supply_cap_fails() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    set_caller(owner(), 0);
+    contract.set_global_supply_cap(Some(2));
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    mint(&mut contract, accounts(1), "2", Some("B"));
+
+    mint(&mut contract, accounts(1), "3", Some("A"));
+}
+
+#[test]
+fn mint_without_global_supply_cap() {
+    let mut contract = new_contract(&[("A", 10)]);
+    for token_id in ["1", "2", "3"].iter() {
+        mint(&mut contract, accounts(1), token_id, Some("A"));
+    }
+
+    assert_eq!(contract.get_global_supply_cap(), None);
+    assert_eq!(contract.nft_total_supply(), U64(3));
+}
+
+#[test]
+fn auto_mint_uses_and_increments_next_token_id() {
+    let mut contract = new_contract(&[]);
+    assert_eq!(contract.get_next_token_id(), U64(1));
+
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(None, sample_token_metadata("auto"), None, None, None, None, None);
+
+    assert!(contract.nft_token_exists("1".to_string()));
+    assert_eq!(contract.get_next_token_id(), U64(2));
+}
+
+#[test]
+fn owner_mints_into_locked_type() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    mint(&mut contract, owner(), "1", Some("A"));
+
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(1));
+}
+
+#[test]
+#[should_panic(expected = "Only the owner can mint tokens of a locked type")]
+fn non_owner_mint_into_locked_type_fails() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    mint(&mut contract, accounts(1), "1", Some("A"));
+}
+
+#[test]
+#[should_panic(expected = "Media already minted for token type")]
+fn duplicate_media_in_unique_media_type_fails() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.set_unique_media_for_type("A".to_string(), true);
+    mint_media(&mut contract, "1", "A", "ipfs://one");
+
+    mint_media(&mut contract, "2", "A", "ipfs://one");
+}
+
+#[test]
+fn distinct_media_in_unique_media_type() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    set_caller(owner(), 0);
+    contract.set_unique_media_for_type("A".to_string(), true);
+    mint_media(&mut contract, "1", "A", "ipfs://one");
+    mint_media(&mut contract, "2", "A", "ipfs://two");
+    // the same media is fine in a type that doesn't require unique media
+    mint_media(&mut contract, "3", "B", "ipfs://one");
+    mint_media(&mut contract, "4", "B", "ipfs://one");
+
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(2));
+    assert_eq!(contract.nft_supply_for_type("B".to_string()), U64(2));
+}
+
+#[test]
+fn creator_royalty_includes_minter() {
+    let mut contract = new_contract(&[]);
+    let royalty: HashMap<AccountId, u32> = [(accounts(2).to_string(), 1000)].iter().cloned().collect();
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), Some(royalty), None, None, None, Some(500));
+
+    let royalty = contract.nft_token_royalty("1".to_string()).unwrap();
+    assert_eq!(royalty.get(accounts(1).as_ref()), Some(&500));
+    assert_eq!(royalty.get(accounts(2).as_ref()), Some(&1000));
+}
+
+#[test]
+#[should_panic(expected = "Perpetual royalties cannot be more than 90%")]
+fn creator_royalty_over_cap_fails() {
+    let mut contract = new_contract(&[]);
+    let royalty: HashMap<AccountId, u32> = [(accounts(2).to_string(), 8000)].iter().cloned().collect();
+    set_caller(accounts(1), MINT_DEPOSIT);
+
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), Some(royalty), None, None, None, Some(1001));
+}
+
+#[test]
+fn mint_beyond_normal_cap_into_unlimited_type() {
+    let mut contract = new_contract(&[("A", UNLIMITED_SUPPLY_CAP)]);
+    for token_id in ["1", "2", "3"].iter() {
+        mint(&mut contract, accounts(1), token_id, Some("A"));
+    }
+
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(3));
+    assert_eq!(contract.get_remaining_supply("A".to_string()), None);
+}
+
+#[test]
+#[should_panic(expected = "Cannot mint anymore of token type.")]
+fn mint_beyond_type_cap_fails() {
+    let mut contract = new_contract(&[("A", 2)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    mint(&mut contract, accounts(1), "2", Some("A"));
+    assert_eq!(contract.get_remaining_supply("A".to_string()), Some(U64(0)));
+
+    mint(&mut contract, accounts(1), "3", Some("A"));
+}
+
+fn mint_with_media_hash(contract: &mut Contract, media_hash: Vec<u8>) {
+    let mut metadata = sample_token_metadata("1");
+    metadata.media = Some("ipfs://one".to_string());
+    metadata.media_hash = Some(Base64VecU8(media_hash));
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), metadata, None, None, None, None, None);
+}
+
+#[test]
+fn mint_with_32_byte_media_hash() {
+    let mut contract = new_contract(&[]);
+
+    mint_with_media_hash(&mut contract, vec![7; 32]);
+
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().metadata.media_hash.unwrap().0, vec![7; 32]);
+}
+
+#[test]
+#[should_panic(expected = "Media hash has to be a 32 bytes sha256 hash")]
+fn mint_with_malformed_media_hash_fails() {
+    let mut contract = new_contract(&[]);
+
+    mint_with_media_hash(&mut contract, vec![7; 31]);
+}
+
+fn remint(contract: &mut Contract, caller: ValidAccountId, description: &str, attached_deposit: Balance) {
+    let mut metadata = sample_token_metadata("1");
+    metadata.description = Some(description.to_string());
+    set_caller(caller, attached_deposit);
+    contract.nft_remint("1".to_string(), metadata);
+}
+
+#[test]
+fn remint_with_larger_metadata_pays_storage() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+
+    remint(&mut contract, accounts(1), &"d".repeat(100), MINT_DEPOSIT);
+
+    let token = contract.nft_token("1".to_string()).unwrap();
+    assert_eq!(token.owner_id, accounts(1).to_string());
+    assert_eq!(token.metadata.description, Some("d".repeat(100)));
+    assert!(transferred_to(&accounts(1)) < MINT_DEPOSIT);
+}
+
+#[test]
+#[should_panic(expected = "Must attach")]
+fn remint_with_larger_metadata_without_deposit_fails() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+
+    remint(&mut contract, accounts(1), &"d".repeat(100), 1);
+}
+
+#[test]
+fn remint_with_smaller_metadata_refunds_storage() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+    remint(&mut contract, accounts(1), &"d".repeat(100), MINT_DEPOSIT);
+
+    remint(&mut contract, accounts(1), "d", 1);
+
+    assert_eq!(transferred_to(&accounts(1)), 99 * env::storage_byte_cost());
+}
+
+#[test]
+fn remint_keeps_issued_at() {
+    let mut contract = new_contract(&[]);
+    testing_env!(get_context(accounts(1), MINT_DEPOSIT).block_timestamp(5_000_000).build());
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), None, None, None, None, None);
+
+    let mut metadata = sample_token_metadata("1");
+    metadata.issued_at = Some("1".to_string());
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_remint("1".to_string(), metadata);
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().metadata.issued_at, Some("5".to_string()));
+
+    let mut metadata = sample_token_metadata("1");
+    metadata.issued_at = None;
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_remint("1".to_string(), metadata);
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().metadata.issued_at, Some("5".to_string()));
+}
+
+#[test]
+fn remint_applies_type_template() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    set_caller(owner(), 0);
+    let mut template = sample_token_metadata("template title");
+    template.description = Some("template description".to_string());
+    contract.set_metadata_template_for_type("A".to_string(), Some(template));
+
+    let mut metadata = sample_token_metadata("new title");
+    metadata.description = None;
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_remint("1".to_string(), metadata);
+
+    let metadata = contract.nft_token("1".to_string()).unwrap().metadata;
+    assert_eq!(metadata.title, Some("new title".to_string()));
+    assert_eq!(metadata.description, Some("template description".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Only the creator can remint")]
+fn remint_by_other_account_fails() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+
+    remint(&mut contract, accounts(2), "d", MINT_DEPOSIT);
+}
+
+#[test]
+fn mint_with_profile_when_required() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    contract.set_require_profile_to_mint(true);
+    set_caller(accounts(1), 0);
+    contract.set_profile(Profile { bio: "bio".to_string(), image: String::new() });
+
+    mint(&mut contract, accounts(1), "1", None);
+
+    assert!(contract.nft_token_exists("1".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Minter must have a profile")]
+fn mint_without_profile_when_required_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    contract.set_require_profile_to_mint(true);
+
+    mint(&mut contract, accounts(1), "1", None);
+}
+
+#[test]
+fn backfill_mint_stores_issued_at() {
+    let mut contract = new_contract(&[]);
+    testing_env!(get_context(owner(), MINT_DEPOSIT).block_timestamp(5_000_000_000).build());
+
+    contract.nft_mint_backfill(Some("1".to_string()), sample_token_metadata("1"), None, None, None, None, None, Some(U64(1_000)));
+    contract.nft_mint(Some("2".to_string()), sample_token_metadata("2"), None, None, None, None, None);
+
+    let issued_at = |token_id: &str| contract.nft_token(token_id.to_string()).unwrap().metadata.issued_at;
+    assert_eq!(issued_at("1"), Some("1000".to_string()));
+    assert_eq!(issued_at("2"), Some("5000".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Owner's method")]
+fn backfill_mint_by_non_owner_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), MINT_DEPOSIT);
+
+    contract.nft_mint_backfill(Some("1".to_string()), sample_token_metadata("1"), None, None, None, None, None, Some(U64(1_000)));
+}
+
+#[test]
+fn metadata_template_fills_unset_fields() {
+    let mut contract = new_contract(&[("A", 10)]);
+    let mut template = sample_token_metadata("template title");
+    template.description = Some("template description".to_string());
+    template.media = Some("ipfs://template".to_string());
+    set_caller(owner(), 0);
+    contract.set_metadata_template_for_type("A".to_string(), Some(template));
+
+    let mut metadata = sample_token_metadata("own title");
+    metadata.media = Some("ipfs://own".to_string());
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), metadata, None, None, Some("A".to_string()), None, None);
+
+    let metadata = contract.nft_token("1".to_string()).unwrap().metadata;
+    assert_eq!(metadata.title, Some("own title".to_string()));
+    assert_eq!(metadata.media, Some("ipfs://own".to_string()));
+    assert_eq!(metadata.description, Some("template description".to_string()));
+}
+
+#[test]
+fn mint_reserved_and_lock_locks_unlocked_type() {
+    let mut contract = new_contract(&[("A", 10)]);
+    assert!(contract.get_token_types_locked().is_empty());
+
+    set_caller(owner(), MINT_DEPOSIT);
+    contract.mint_reserved_and_lock(Some("1".to_string()), sample_token_metadata("1"), Some(accounts(1)), "A".to_string());
+
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().owner_id, accounts(1).to_string());
+    assert_eq!(contract.get_token_types_locked(), vec!["A".to_string()]);
+}
+
+#[test]
+fn mint_reserved_and_lock_keeps_locked_type_locked() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    set_caller(owner(), MINT_DEPOSIT);
+    contract.mint_reserved_and_lock(Some("1".to_string()), sample_token_metadata("1"), None, "A".to_string());
+
+    assert_eq!(contract.get_token_types_locked(), vec!["A".to_string()]);
+}
+
+fn mint_at(contract: &mut Contract, token_id: &str, block_timestamp: u64) {
+    testing_env!(get_context(accounts(1), MINT_DEPOSIT).block_timestamp(block_timestamp).build());
+    contract.nft_mint(Some(token_id.to_string()), sample_token_metadata(token_id), None, None, Some("A".to_string()), None, None);
+}
+
+#[test]
+fn open_edition_mints_without_cap_until_deadline() {
+    let mut contract = new_contract(&[("A", 1)]);
+    set_caller(owner(), 0);
+    contract.set_open_edition("A".to_string(), U64(1_000));
+
+    mint_at(&mut contract, "1", 0);
+    mint_at(&mut contract, "2", 999);
+
+    assert_eq!(contract.nft_supply_for_type("A".to_string()), U64(2));
+    assert_eq!(contract.get_remaining_supply("A".to_string()), None);
+}
+
+#[test]
+#[should_panic(expected = "Open edition has ended")]
+fn open_edition_mint_after_deadline_fails() {
+    let mut contract = new_contract(&[("A", 1)]);
+    set_caller(owner(), 0);
+    contract.set_open_edition("A".to_string(), U64(1_000));
+
+    mint_at(&mut contract, "1", 1_000);
+}
+
+#[test]
+fn open_edition_sold_out_after_deadline_before_finalize() {
+    let mut contract = new_contract(&[("A", 1)]);
+    set_caller(owner(), 0);
+    contract.set_open_edition("A".to_string(), U64(1_000));
+    mint_at(&mut contract, "1", 0);
+    assert!(!contract.nft_type_sold_out("A".to_string()));
+
+    testing_env!(get_context(accounts(1), 0).block_timestamp(1_000).build());
+
+    assert!(contract.nft_type_sold_out("A".to_string()));
+    assert_eq!(contract.get_remaining_supply("A".to_string()), Some(U64(0)));
+}
+
+#[test]
+fn finalize_open_edition_caps_minted_supply() {
+    let mut contract = new_contract(&[("A", 1)]);
+    set_caller(owner(), 0);
+    contract.set_open_edition("A".to_string(), U64(1_000));
+    mint_at(&mut contract, "1", 0);
+    mint_at(&mut contract, "2", 0);
+
+    testing_env!(get_context(accounts(1), 0).block_timestamp(1_000).build());
+    contract.finalize_open_edition("A".to_string());
+
+    assert_eq!(contract.get_supply_caps().get("A"), Some(&U64(2)));
+    assert_eq!(contract.get_open_edition_until("A".to_string()), None);
+    assert!(contract.nft_type_sold_out("A".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Open edition has not ended yet")]
+fn finalize_open_edition_before_deadline_fails() {
+    let mut contract = new_contract(&[("A", 1)]);
+    set_caller(owner(), 0);
+    contract.set_open_edition("A".to_string(), U64(1_000));
+
+    contract.finalize_open_edition("A".to_string());
+}
+
+#[test]
+fn mint_with_valid_royalty_account() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), MINT_DEPOSIT);
+    let royalty: HashMap<AccountId, u32> = [(accounts(2).to_string(), 500)].iter().cloned().collect();
+
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), Some(royalty.clone()), None, None, None, None);
+
+    assert_eq!(contract.nft_token_royalty("1".to_string()), Some(royalty));
+}
+
+#[test]
+#[should_panic(expected = "Invalid royalty account id")]
+fn mint_with_invalid_royalty_account_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), MINT_DEPOSIT);
+    let royalty = [("Invalid Account".to_string(), 500)].iter().cloned().collect();
+
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), Some(royalty), None, None, None, None);
+}
@@ -0,0 +1,120 @@
+use std::convert::TryInto;
+
+use near_sdk::test_utils::{accounts, get_created_receipts, testing_env_with_promise_results, VMContextBuilder};
+use near_sdk::{serde_json, testing_env, MockedBlockchain, PromiseResult};
+
+use crate::*;
+
+mod admin;
+mod burn;
+mod free_mint;
+mod migration;
+mod mint;
+mod nft_core;
+mod payout;
+mod profile;
+mod views;
+
+/// more than any test mint needs, the excess is refunded
+pub(crate) const MINT_DEPOSIT: Balance = 1_000_000_000_000_000_000_000_000;
+
+pub(crate) fn contract_account() -> ValidAccountId {
+    "pluminite.near".try_into().unwrap()
+}
+
+pub(crate) fn owner() -> ValidAccountId {
+    accounts(0)
+}
+
+pub(crate) fn get_context(predecessor_account_id: ValidAccountId, attached_deposit: Balance) -> VMContextBuilder {
+    let mut builder = VMContextBuilder::new();
+    builder
+        .current_account_id(contract_account())
+        .signer_account_id(predecessor_account_id.clone())
+        .predecessor_account_id(predecessor_account_id)
+        .attached_deposit(attached_deposit);
+    builder
+}
+
+/// every call gets a fresh context (and gas), the contract storage is kept
+pub(crate) fn set_caller(predecessor_account_id: ValidAccountId, attached_deposit: Balance) {
+    testing_env!(get_context(predecessor_account_id, attached_deposit).build());
+}
+
+/// callbacks run as the contract itself, with the result of the promise they were chained to
+pub(crate) fn set_callback_result(promise_result: PromiseResult) {
+    testing_env_with_promise_results(get_context(contract_account(), 0).build(), promise_result);
+}
+
+/// sum of the transfers to account_id made by the current call, receipts only expose their fields as JSON
+pub(crate) fn transferred_to(account_id: &ValidAccountId) -> Balance {
+    let receiver = format!("\"receiver_id\":\"{}\"", account_id);
+    get_created_receipts()
+        .iter()
+        .map(|receipt| serde_json::to_string(receipt).unwrap())
+        .filter(|receipt| receipt.contains(&receiver))
+        .flat_map(|receipt| {
+            receipt
+                .split("\"Transfer\":{\"deposit\":")
+                .skip(1)
+                .map(|transfer| transfer.split('}').next().unwrap().parse::<Balance>().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .sum()
+}
+
+pub(crate) fn sample_nft_metadata() -> NFTMetadata {
+    NFTMetadata {
+        spec: "nft-1.0.0".to_string(),
+        name: "Pluminite".to_string(),
+        symbol: "PLM".to_string(),
+        icon: None,
+        base_uri: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+pub(crate) fn sample_token_metadata(title: &str) -> TokenMetadata {
+    TokenMetadata {
+        title: Some(title.to_string()),
+        description: None,
+        media: None,
+        media_hash: None,
+        copies: None,
+        issued_at: None,
+        expires_at: None,
+        starts_at: None,
+        updated_at: None,
+        extra: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+/// unlocked token types, storage paid by the minters
+pub(crate) fn new_contract(supply_caps: &[(&str, u64)]) -> Contract {
+    new_contract_with(supply_caps, false, 0)
+}
+
+pub(crate) fn new_contract_with(supply_caps: &[(&str, u64)], use_storage_fees: bool, free_mints: u64) -> Contract {
+    set_caller(owner(), 0);
+    let supply_cap_by_type = supply_caps
+        .iter()
+        .map(|(token_type, supply_cap)| (token_type.to_string(), U64(*supply_cap)))
+        .collect();
+    Contract::new(owner(), sample_nft_metadata(), supply_cap_by_type, use_storage_fees, free_mints, Some(true))
+}
+
+pub(crate) fn mint(contract: &mut Contract, minter: ValidAccountId, token_id: &str, token_type: Option<&str>) {
+    set_caller(minter, MINT_DEPOSIT);
+    contract.nft_mint(
+        Some(token_id.to_string()),
+        sample_token_metadata(token_id),
+        None,
+        None,
+        token_type.map(str::to_string),
+        None,
+        None,
+    );
+}
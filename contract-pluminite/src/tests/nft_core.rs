@@ -0,0 +1,430 @@
+use near_sdk::test_utils::accounts;
+
+use super::*;
+
+fn transfer_call(contract: &mut Contract, sender: ValidAccountId, receiver: ValidAccountId, token_id: &str) {
+    set_caller(sender, 1);
+    contract.nft_transfer_call(receiver, token_id.to_string(), None, None, "{}".to_string());
+}
+
+fn resolve_transfer(contract: &mut Contract, promise_result: PromiseResult) -> bool {
+    set_callback_result(promise_result);
+    contract.nft_resolve_transfer(
+        accounts(1).into(),
+        accounts(2).into(),
+        HashMap::new(),
+        HashMap::new(),
+        "1".to_string(),
+    )
+}
+
+#[test]
+fn nft_resolve_transfer_keeps_token_with_receiver() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    transfer_call(&mut contract, accounts(1), accounts(2), "1");
+
+    assert!(resolve_transfer(&mut contract, PromiseResult::Successful(b"false".to_vec())));
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().owner_id, accounts(2).to_string());
+}
+
+#[test]
+fn nft_resolve_transfer_returns_token_to_owner() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    transfer_call(&mut contract, accounts(1), accounts(2), "1");
+
+    assert!(!resolve_transfer(&mut contract, PromiseResult::Successful(b"true".to_vec())));
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().owner_id, accounts(1).to_string());
+    assert_eq!(contract.nft_supply_for_owner(accounts(2).into()), U128(0));
+}
+
+#[test]
+fn nft_resolve_transfer_returns_token_when_receiver_fails() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    transfer_call(&mut contract, accounts(1), accounts(2), "1");
+
+    assert!(!resolve_transfer(&mut contract, PromiseResult::Failed));
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().owner_id, accounts(1).to_string());
+}
+
+fn mint_soulbound(contract: &mut Contract, token_id: &str) {
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some(token_id.to_string()), sample_token_metadata(token_id), None, None, None, Some(false), None);
+}
+
+#[test]
+#[should_panic(expected = "Token is soulbound")]
+fn soulbound_token_transfer_fails() {
+    let mut contract = new_contract(&[]);
+    mint_soulbound(&mut contract, "1");
+    assert!(!contract.nft_token("1".to_string()).unwrap().transferable);
+
+    set_caller(accounts(1), 1);
+    contract.nft_transfer(accounts(2), "1".to_string(), None, None);
+}
+
+#[test]
+fn soulbound_token_burned_by_owner() {
+    let mut contract = new_contract(&[]);
+    mint_soulbound(&mut contract, "1");
+
+    set_caller(accounts(1), 1);
+    contract.nft_burn("1".to_string());
+
+    assert!(!contract.nft_token_exists("1".to_string()));
+    assert!(!contract.soulbound_tokens.contains(&"1".to_string()));
+}
+
+fn approve(contract: &mut Contract, token_id: &str, account_id: ValidAccountId) -> u64 {
+    set_caller(accounts(1), 1);
+    contract.nft_approve(token_id.to_string(), account_id, None, None)
+}
+
+#[test]
+fn nft_approve_returns_incrementing_approval_ids() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+
+    let first_approval_id = approve(&mut contract, "1", accounts(2));
+    let second_approval_id = approve(&mut contract, "1", accounts(3));
+
+    assert_eq!(second_approval_id, first_approval_id + 1);
+    assert!(contract.nft_is_approved("1".to_string(), accounts(2).into(), Some(U64(first_approval_id))));
+    assert!(contract.nft_is_approved("1".to_string(), accounts(3).into(), Some(U64(second_approval_id))));
+    assert!(!contract.nft_is_approved("1".to_string(), accounts(2).into(), Some(U64(second_approval_id))));
+}
+
+fn media_of_token_with(contract: &mut Contract, token_id: &str, media: &str) -> String {
+    let mut metadata = sample_token_metadata(token_id);
+    metadata.media = Some(media.to_string());
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some(token_id.to_string()), metadata, None, None, None, None, None);
+    contract.nft_token(token_id.to_string()).unwrap().metadata.media.unwrap()
+}
+
+fn new_contract_with_base_uri() -> Contract {
+    let mut contract = new_contract(&[]);
+    let mut metadata = sample_nft_metadata();
+    metadata.base_uri = Some("https://gateway.example/ipfs/".to_string());
+    set_caller(owner(), 0);
+    contract.nft_set_metadata(metadata);
+    contract
+}
+
+#[test]
+fn relative_media_resolved_against_base_uri() {
+    let mut contract = new_contract_with_base_uri();
+
+    assert_eq!(media_of_token_with(&mut contract, "1", "media/1.png"), "https://gateway.example/ipfs/media/1.png");
+    assert_eq!(media_of_token_with(&mut contract, "2", "/media/2.png"), "https://gateway.example/ipfs/media/2.png");
+}
+
+#[test]
+fn absolute_media_untouched() {
+    let mut contract = new_contract_with_base_uri();
+    let cid_v0 = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+    let cid_v1 = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    assert_eq!(media_of_token_with(&mut contract, "1", "https://example.com/1.png"), "https://example.com/1.png");
+    assert_eq!(media_of_token_with(&mut contract, "2", "ipfs://media/2.png"), "ipfs://media/2.png");
+    assert_eq!(media_of_token_with(&mut contract, "3", cid_v0), cid_v0);
+    assert_eq!(media_of_token_with(&mut contract, "4", &format!("{}/3.png", cid_v1)), format!("{}/3.png", cid_v1));
+}
+
+#[test]
+fn relative_media_without_base_uri_untouched() {
+    let mut contract = new_contract(&[]);
+
+    assert_eq!(media_of_token_with(&mut contract, "1", "media/1.png"), "media/1.png");
+}
+
+#[test]
+fn nft_approve_batch_approves_every_token() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    mint(&mut contract, accounts(1), "2", None);
+    approve(&mut contract, "2", accounts(3));
+
+    set_caller(accounts(1), 1);
+    let approval_ids = contract.nft_approve_batch(vec!["1".to_string(), "2".to_string()], accounts(2), None, None);
+
+    assert_eq!(approval_ids, vec![0, 1]);
+    assert!(contract.nft_is_approved("1".to_string(), accounts(2).into(), Some(U64(0))));
+    assert!(contract.nft_is_approved("2".to_string(), accounts(2).into(), Some(U64(1))));
+}
+
+#[test]
+#[should_panic(expected = "Predecessor must be the token owner.")]
+fn nft_approve_batch_with_non_owned_token_fails() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    mint(&mut contract, accounts(2), "2", None);
+
+    set_caller(accounts(1), 1);
+    contract.nft_approve_batch(vec!["1".to_string(), "2".to_string()], accounts(3), None, None);
+}
+
+#[test]
+fn nft_approve_batch_with_msg_shares_prepaid_gas() {
+    let mut contract = new_contract(&[]);
+    let token_ids: Vec<TokenId> = (0..5).map(|i| i.to_string()).collect();
+    for token_id in &token_ids {
+        mint(&mut contract, accounts(1), token_id, None);
+    }
+
+    set_caller(accounts(1), 1);
+    let approval_ids = contract.nft_approve_batch(token_ids, accounts(2), Some("{}".to_string()), None);
+
+    assert_eq!(approval_ids, vec![0; 5]);
+}
+
+#[test]
+#[should_panic(expected = "Not enough gas to call nft_on_approve for every approval")]
+fn nft_approve_batch_with_msg_without_enough_gas_fails() {
+    let mut contract = new_contract(&[]);
+    let token_ids: Vec<TokenId> = (0..MAX_PAGE_SIZE).map(|i| i.to_string()).collect();
+    for token_id in &token_ids {
+        mint(&mut contract, accounts(1), token_id, None);
+    }
+
+    set_caller(accounts(1), 1);
+    contract.nft_approve_batch(token_ids, accounts(2), Some("{}".to_string()), None);
+}
+
+fn approve_until(contract: &mut Contract, account_id: ValidAccountId, expires_at: Option<u64>, attached_deposit: Balance) {
+    set_caller(accounts(1), attached_deposit);
+    contract.nft_approve("1".to_string(), account_id, None, expires_at.map(U64));
+}
+
+fn transfer_payout_by(contract: &mut Contract, sender: ValidAccountId, block_timestamp: u64) {
+    testing_env!(get_context(sender, 1).block_timestamp(block_timestamp).build());
+    contract.nft_transfer_payout(accounts(4), "1".to_string(), None, None, Some(U128(100)), None);
+}
+
+fn is_approved_at(contract: &Contract, account_id: ValidAccountId, block_timestamp: u64) -> bool {
+    testing_env!(get_context(accounts(1), 0).block_timestamp(block_timestamp).build());
+    contract.nft_is_approved("1".to_string(), account_id.into(), None)
+}
+
+#[test]
+fn approval_used_before_expiry() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    approve_until(&mut contract, accounts(2), Some(1_000), 1);
+    assert!(is_approved_at(&contract, accounts(2), 999));
+
+    transfer_payout_by(&mut contract, accounts(2), 999);
+
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().owner_id, accounts(4).to_string());
+    assert!(contract.approvals_expire_at.get(&"1".to_string()).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Approval expired")]
+fn approval_used_after_expiry_fails() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    approve_until(&mut contract, accounts(2), Some(1_000), 1);
+    assert!(!is_approved_at(&contract, accounts(2), 1_000));
+
+    transfer_payout_by(&mut contract, accounts(2), 1_000);
+}
+
+#[test]
+fn approval_without_expiry_never_expires() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    approve_until(&mut contract, accounts(2), None, 1);
+    assert!(is_approved_at(&contract, accounts(2), u64::MAX));
+
+    transfer_payout_by(&mut contract, accounts(2), u64::MAX);
+
+    assert_eq!(contract.nft_token("1".to_string()).unwrap().owner_id, accounts(4).to_string());
+}
+
+#[test]
+#[should_panic(expected = "Approval expiry must be in the future")]
+fn approval_expiring_in_the_past_fails() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+
+    testing_env!(get_context(accounts(1), 1).block_timestamp(1_000).build());
+    contract.nft_approve("1".to_string(), accounts(2), None, Some(U64(1_000)));
+}
+
+#[test]
+fn approval_with_expiry_refunded_what_it_paid() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+    let account_id: AccountId = accounts(2).into();
+    let paid = Balance::from(bytes_for_approved_account_id(&account_id) + bytes_for_approval_expiry(&account_id))
+        * env::storage_byte_cost();
+    approve_until(&mut contract, accounts(2), Some(1_000), paid);
+    assert_eq!(transferred_to(&accounts(1)), 0);
+
+    set_caller(accounts(1), 1);
+    contract.nft_revoke("1".to_string(), accounts(2));
+
+    assert_eq!(transferred_to(&accounts(1)), paid);
+    assert!(contract.approvals_expire_at.get(&"1".to_string()).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Must attach")]
+fn approval_with_expiry_without_deposit_for_expiry_fails() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+    let paid = Balance::from(bytes_for_approved_account_id(&accounts(2).into())) * env::storage_byte_cost();
+
+    approve_until(&mut contract, accounts(2), Some(1_000), paid);
+}
+
+#[test]
+fn approval_without_expiry_refunded_what_it_paid() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+    let paid = Balance::from(bytes_for_approved_account_id(&accounts(2).into())) * env::storage_byte_cost();
+    approve_until(&mut contract, accounts(2), None, paid);
+
+    set_caller(accounts(1), 1);
+    contract.nft_revoke_all("1".to_string());
+
+    assert_eq!(transferred_to(&accounts(1)), paid);
+}
+
+#[test]
+fn nft_approved_count_counts_tokens_with_approvals() {
+    let mut contract = new_contract(&[]);
+    for token_id in ["1", "2", "3"].iter() {
+        mint(&mut contract, accounts(1), token_id, None);
+    }
+    approve(&mut contract, "1", accounts(2));
+    approve(&mut contract, "1", accounts(3));
+    approve(&mut contract, "2", accounts(2));
+
+    assert_eq!(contract.nft_approved_count(accounts(1).into()), U64(2));
+    assert_eq!(contract.nft_approved_count(accounts(2).into()), U64(0));
+}
+
+#[test]
+fn last_transfer_follows_mint_and_transfers() {
+    let mut contract = new_contract(&[]);
+    testing_env!(get_context(accounts(1), MINT_DEPOSIT).block_timestamp(100).build());
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), None, None, None, None, None);
+    assert_eq!(contract.nft_token_last_transfer("1".to_string()), Some(U64(100)));
+
+    testing_env!(get_context(accounts(1), 1).block_timestamp(200).build());
+    contract.nft_transfer(accounts(2), "1".to_string(), None, None);
+
+    assert_eq!(contract.nft_token_last_transfer("1".to_string()), Some(U64(200)));
+    assert_eq!(contract.nft_token_full("1".to_string()).unwrap().last_transfer_at, Some(U64(200)));
+    assert_eq!(contract.nft_token_last_transfer("2".to_string()), None);
+}
+
+#[test]
+fn emptied_owner_set_is_removed() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+
+    set_caller(accounts(1), 1);
+    contract.nft_transfer(accounts(2), "1".to_string(), None, None);
+
+    assert!(contract.tokens_per_owner.get(&accounts(1).into()).is_none());
+    assert!(!contract.holders.contains(&accounts(1).into()));
+    assert!(contract.holders.contains(&accounts(2).into()));
+}
+
+#[test]
+fn emptied_type_set_is_removed() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+
+    set_caller(accounts(1), 1);
+    contract.nft_burn("1".to_string());
+
+    assert!(contract.tokens_per_type.get(&"A".to_string()).is_none());
+    assert!(contract.tokens_per_owner.get(&accounts(1).into()).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Must attach")]
+fn new_approval_without_storage_deposit_fails() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+
+    approve_until(&mut contract, accounts(2), None, 1);
+}
+
+#[test]
+fn re_approval_needs_no_storage_deposit() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+    let paid = Balance::from(bytes_for_approved_account_id(&accounts(2).into())) * env::storage_byte_cost();
+    approve_until(&mut contract, accounts(2), None, paid);
+
+    approve_until(&mut contract, accounts(2), None, 1);
+
+    let token = contract.nft_token("1".to_string()).unwrap();
+    assert_eq!(token.approved_account_ids.get(accounts(2).as_ref()), Some(&U64(1)));
+}
+
+#[test]
+fn admin_clear_approvals_refunds_token_owner() {
+    let mut contract = new_contract_with(&[], true, 0);
+    mint(&mut contract, accounts(1), "1", None);
+    let account_id: AccountId = accounts(2).into();
+    let paid = Balance::from(bytes_for_approved_account_id(&account_id) + bytes_for_approval_expiry(&account_id))
+        * env::storage_byte_cost();
+    approve_until(&mut contract, accounts(2), Some(1_000), paid);
+
+    set_caller(owner(), 1);
+    contract.admin_clear_approvals("1".to_string());
+
+    assert!(contract.nft_token("1".to_string()).unwrap().approved_account_ids.is_empty());
+    assert!(contract.approvals_expire_at.get(&"1".to_string()).is_none());
+    assert_eq!(transferred_to(&accounts(1)), paid);
+}
+
+#[test]
+#[should_panic(expected = "Owner's method")]
+fn admin_clear_approvals_by_non_owner_fails() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    approve(&mut contract, "1", accounts(2));
+
+    set_caller(accounts(1), 1);
+    contract.admin_clear_approvals("1".to_string());
+}
+
+fn approve_many(contract: &mut Contract, token_id: &str, count: usize) {
+    for i in 0..count {
+        approve(contract, token_id, format!("market{}.near", i).try_into().unwrap());
+    }
+}
+
+#[test]
+fn nft_approve_up_to_max_approvals() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+
+    approve_many(&mut contract, "1", MAX_APPROVALS_PER_TOKEN);
+    // re-approving an approved account replaces its approval, even at the limit
+    let approval_id = approve(&mut contract, "1", "market0.near".try_into().unwrap());
+
+    let approved_account_ids = contract.nft_token("1".to_string()).unwrap().approved_account_ids;
+    assert_eq!(approved_account_ids.len(), MAX_APPROVALS_PER_TOKEN);
+    assert_eq!(approval_id, MAX_APPROVALS_PER_TOKEN as u64);
+    assert_eq!(approved_account_ids["market0.near"], U64(approval_id));
+}
+
+#[test]
+#[should_panic(expected = "Too many approvals, revoke one first")]
+fn nft_approve_past_max_approvals_fails() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+    approve_many(&mut contract, "1", MAX_APPROVALS_PER_TOKEN);
+
+    approve(&mut contract, "1", accounts(2));
+}
@@ -0,0 +1,184 @@
+use near_sdk::test_utils::accounts;
+
+use super::*;
+
+/// token "1" owned by accounts(1)
+fn mint_with_royalty(contract: &mut Contract, royalty: &[(ValidAccountId, u32)]) {
+    set_caller(accounts(1), MINT_DEPOSIT);
+    let royalty = royalty
+        .iter()
+        .map(|(account_id, amount)| (account_id.to_string(), *amount))
+        .collect();
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), Some(royalty), None, None, None, None);
+}
+
+fn sell(contract: &mut Contract, balance: Balance) -> Payout {
+    set_caller(accounts(1), 1);
+    contract
+        .nft_transfer_payout(accounts(3), "1".to_string(), None, None, Some(U128(balance)), None)
+        .unwrap()
+}
+
+#[test]
+fn payout_without_contract_royalty_omits_owner() {
+    let mut contract = new_contract(&[]);
+    mint_with_royalty(&mut contract, &[(accounts(2), 1000)]);
+
+    let payout = sell(&mut contract, 10_000);
+
+    assert_eq!(payout.len(), 2);
+    assert!(!payout.contains_key(owner().as_ref()));
+    assert_eq!(payout[accounts(2).as_ref()], U128(1_000));
+    assert_eq!(payout[accounts(1).as_ref()], U128(9_000));
+}
+
+#[test]
+fn payout_with_max_contract_royalty() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    contract.set_contract_royalty(CONTRACT_ROYALTY_CAP);
+    mint_with_royalty(&mut contract, &[(accounts(2), 1000)]);
+
+    let payout = sell(&mut contract, 10_000);
+
+    assert_eq!(payout[owner().as_ref()], U128(1_000));
+    assert_eq!(payout[accounts(2).as_ref()], U128(1_000));
+    assert_eq!(payout[accounts(1).as_ref()], U128(8_000));
+}
+
+#[test]
+#[should_panic(expected = "Contract royalties limited to 10% for owner")]
+fn contract_royalty_above_cap_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+
+    contract.set_contract_royalty(CONTRACT_ROYALTY_CAP + 1);
+}
+
+#[test]
+fn payout_omits_zero_royalty() {
+    let mut contract = new_contract(&[]);
+    mint_with_royalty(&mut contract, &[(accounts(2), 0), (accounts(4), 500)]);
+
+    let payout = sell(&mut contract, 10_000);
+
+    assert!(!payout.contains_key(accounts(2).as_ref()));
+    assert_eq!(payout[accounts(4).as_ref()], U128(500));
+    assert_eq!(payout[accounts(1).as_ref()], U128(9_500));
+}
+
+fn mint_typed(contract: &mut Contract, token_type: &str) {
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), None, None, Some(token_type.to_string()), None, None);
+}
+
+#[test]
+fn payout_with_contract_royalty_for_type() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.set_contract_royalty(200);
+    contract.set_contract_royalty_for_type("A".to_string(), Some(500));
+    mint_typed(&mut contract, "A");
+
+    let payout = sell(&mut contract, 10_000);
+
+    assert_eq!(contract.get_contract_royalty_for_type("A".to_string()), 500);
+    assert_eq!(payout[owner().as_ref()], U128(500));
+    assert_eq!(payout[accounts(1).as_ref()], U128(9_500));
+}
+
+#[test]
+fn payout_falls_back_to_global_contract_royalty() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    set_caller(owner(), 0);
+    contract.set_contract_royalty(200);
+    contract.set_contract_royalty_for_type("A".to_string(), Some(500));
+    mint_typed(&mut contract, "B");
+
+    let payout = sell(&mut contract, 10_000);
+
+    assert_eq!(contract.get_contract_royalty_for_type("B".to_string()), 200);
+    assert_eq!(payout[owner().as_ref()], U128(200));
+    assert_eq!(payout[accounts(1).as_ref()], U128(9_800));
+}
+
+#[test]
+fn contract_royalty_set_back_to_zero_shrinks_payout() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    contract.set_contract_royalty(500);
+    mint_with_royalty(&mut contract, &[(accounts(2), 1000)]);
+    let payout = sell(&mut contract, 10_000);
+    assert_eq!(payout.len(), 3);
+
+    set_caller(owner(), 0);
+    contract.set_contract_royalty(0);
+    set_caller(accounts(3), 1);
+    let payout = contract
+        .nft_transfer_payout(accounts(1), "1".to_string(), None, None, Some(U128(10_000)), None)
+        .unwrap();
+
+    assert_eq!(contract.get_contract_royalty(), 0);
+    assert_eq!(payout.len(), 2);
+    assert!(!payout.contains_key(owner().as_ref()));
+    assert_eq!(payout[accounts(3).as_ref()], U128(9_000));
+}
+
+fn total(payout: &Payout) -> Balance {
+    payout.values().map(|amount| amount.0).sum()
+}
+
+#[test]
+fn transfer_fee_deducted_before_royalties() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    contract.set_transfer_fee(TRANSFER_FEE_CAP);
+    contract.set_contract_royalty(500);
+    mint_with_royalty(&mut contract, &[(accounts(2), 1000)]);
+
+    let payout = sell(&mut contract, 10_000);
+
+    assert_eq!(payout[accounts(2).as_ref()], U128(950));
+    assert_eq!(payout[owner().as_ref()], U128(500 + 475));
+    assert_eq!(payout[accounts(1).as_ref()], U128(8_075));
+    assert_eq!(total(&payout), 10_000);
+}
+
+#[test]
+fn transfer_fee_conserves_total_at_max_royalties() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    contract.set_transfer_fee(TRANSFER_FEE_CAP);
+    contract.set_contract_royalty(CONTRACT_ROYALTY_CAP);
+    mint_with_royalty(&mut contract, &[(accounts(2), MINTER_ROYALTY_CAP)]);
+
+    let payout = sell(&mut contract, 1_000_000_007);
+
+    assert_eq!(total(&payout), 1_000_000_007);
+}
+
+#[test]
+#[should_panic(expected = "Transfer fee limited to 5%")]
+fn transfer_fee_above_cap_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+
+    contract.set_transfer_fee(TRANSFER_FEE_CAP + 1);
+}
+
+#[test]
+fn rounding_remainder_goes_to_seller() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+    contract.set_contract_royalty(333);
+    mint_with_royalty(&mut contract, &[(accounts(2), 333), (accounts(4), 333)]);
+
+    let payout = sell(&mut contract, 9_999);
+
+    // 3.33% of 9_999 rounds down to 332 for each of the three royalties
+    assert_eq!(payout[owner().as_ref()], U128(332));
+    assert_eq!(payout[accounts(2).as_ref()], U128(332));
+    assert_eq!(payout[accounts(4).as_ref()], U128(332));
+    assert_eq!(payout[accounts(1).as_ref()], U128(9_999 - 3 * 332));
+    assert_eq!(total(&payout), 9_999);
+}
@@ -0,0 +1,230 @@
+use near_sdk::test_utils::accounts;
+
+use super::*;
+
+fn profile(bio: &str, image: &str) -> Profile {
+    Profile {
+        bio: bio.to_string(),
+        image: image.to_string(),
+    }
+}
+
+#[test]
+fn update_profile_bio_keeps_image() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+    contract.set_profile(profile("bio", "https://image"));
+
+    contract.update_profile_bio("new bio".to_string());
+
+    let profile = contract.get_profile(accounts(1)).unwrap();
+    assert_eq!(profile.bio, "new bio");
+    assert_eq!(profile.image, "https://image");
+}
+
+#[test]
+fn update_profile_image_keeps_bio() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+    contract.set_profile(profile("bio", "https://image"));
+
+    contract.update_profile_image("ipfs://new".to_string());
+
+    let profile = contract.get_profile(accounts(1)).unwrap();
+    assert_eq!(profile.bio, "bio");
+    assert_eq!(profile.image, "ipfs://new");
+}
+
+#[test]
+fn update_profile_bio_without_profile() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+
+    contract.update_profile_bio("bio".to_string());
+
+    let profile = contract.get_profile(accounts(1)).unwrap();
+    assert_eq!(profile.bio, "bio");
+    assert_eq!(profile.image, "");
+}
+
+#[test]
+#[should_panic(expected = "Profile bio length is too long")]
+fn update_profile_bio_too_long_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+
+    contract.update_profile_bio("b".repeat(MAX_PROFILE_BIO_LENGTH));
+}
+
+#[test]
+#[should_panic(expected = "Profile image length is too long")]
+fn update_profile_image_too_long_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+
+    contract.update_profile_image(format!("https://{}", "i".repeat(MAX_PROFILE_IMAGE_LENGTH)));
+}
+
+#[test]
+fn profile_image_with_valid_scheme() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+
+    for image in ["", "https://image", "ipfs://image", "data:image/png;base64,AAAA"].iter() {
+        contract.set_profile(profile("bio", image));
+        assert_eq!(contract.get_profile(accounts(1)).unwrap().image, *image);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Profile image must be an https://, ipfs:// or data:image/ URI")]
+fn profile_image_with_invalid_scheme_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+
+    contract.set_profile(profile("bio", "javascript:alert(1)"));
+}
+
+#[test]
+fn update_profile_bio_of_profile_stored_before_image_checks() {
+    let mut contract = new_contract(&[]);
+    contract.internal_set_profile(&accounts(1).into(), &profile("bio", "http://image"));
+    set_caller(accounts(1), 0);
+
+    contract.update_profile_bio("new bio".to_string());
+
+    assert_eq!(contract.get_profile(accounts(1)).unwrap().image, "http://image");
+}
+
+#[test]
+fn hidden_profile_not_returned() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+    contract.set_profile(profile("bio", ""));
+
+    contract.set_profile_hidden(true);
+    assert!(contract.get_profile(accounts(1)).is_none());
+    // updating the profile keeps it hidden
+    contract.set_profile(profile("new bio", ""));
+    assert!(contract.get_profile(accounts(1)).is_none());
+
+    contract.set_profile_hidden(false);
+    assert_eq!(contract.get_profile(accounts(1)).unwrap().bio, "new bio");
+}
+
+#[test]
+fn deleted_profile_is_no_longer_hidden() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+    contract.set_profile(profile("bio", ""));
+    contract.set_profile_hidden(true);
+
+    contract.delete_profile();
+    contract.set_profile(profile("bio", ""));
+
+    assert!(contract.get_profile(accounts(1)).is_some());
+}
+
+#[test]
+fn import_profiles_skips_existing_profiles() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), 0);
+    contract.set_profile(profile("mine", ""));
+
+    set_caller(owner(), 0);
+    contract.import_profiles(vec![(accounts(1), profile("imported", "")), (accounts(2), profile("imported", ""))]);
+
+    assert_eq!(contract.get_profile(accounts(1)).unwrap().bio, "mine");
+    assert_eq!(contract.get_profile(accounts(2)).unwrap().bio, "imported");
+}
+
+#[test]
+#[should_panic(expected = "Cannot import more than 50 profiles at once")]
+fn import_profiles_over_batch_size_fails() {
+    let mut contract = new_contract(&[]);
+    let entries = (0..=MAX_PROFILE_IMPORT_BATCH)
+        .map(|i| (format!("account{}.near", i).try_into().unwrap(), profile("imported", "")))
+        .collect();
+    set_caller(owner(), 0);
+
+    contract.import_profiles(entries);
+}
+
+#[test]
+#[should_panic(expected = "Profile bio length is too long")]
+fn import_profiles_with_invalid_profile_fails() {
+    let mut contract = new_contract(&[]);
+    set_caller(owner(), 0);
+
+    contract.import_profiles(vec![(accounts(1), profile(&"b".repeat(MAX_PROFILE_BIO_LENGTH), ""))]);
+}
+
+#[test]
+fn profile_accounts_follow_set_and_delete() {
+    let mut contract = new_contract(&[]);
+    for account_id in [accounts(1), accounts(2)].iter() {
+        set_caller(account_id.clone(), 0);
+        contract.set_profile(profile("bio", ""));
+    }
+    contract.update_profile_bio("again".to_string());
+    assert_eq!(
+        contract.get_profile_accounts(U64(0), 10),
+        vec![accounts(1).to_string(), accounts(2).to_string()]
+    );
+
+    set_caller(accounts(1), 0);
+    contract.delete_profile();
+
+    assert_eq!(contract.get_profile_accounts(U64(0), 10), vec![accounts(2).to_string()]);
+}
+
+#[test]
+fn hidden_profiles_left_out_of_profile_accounts() {
+    let mut contract = new_contract(&[]);
+    for account_id in [accounts(1), accounts(2)].iter() {
+        set_caller(account_id.clone(), 0);
+        contract.set_profile(profile("bio", ""));
+    }
+    set_caller(accounts(1), 0);
+    contract.set_profile_hidden(true);
+
+    assert_eq!(contract.get_profile_accounts(U64(0), 10), vec![accounts(2).to_string()]);
+
+    contract.set_profile_hidden(false);
+    assert_eq!(
+        contract.get_profile_accounts(U64(0), 10),
+        vec![accounts(1).to_string(), accounts(2).to_string()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Must attach")]
+fn growing_profile_without_deposit_fails() {
+    let mut contract = new_contract_with(&[], true, 0);
+    set_caller(accounts(1), 0);
+
+    contract.set_profile(profile("bio", ""));
+}
+
+#[test]
+fn growing_profile_with_deposit_refunds_excess() {
+    let mut contract = new_contract_with(&[], true, 0);
+    set_caller(accounts(1), MINT_DEPOSIT);
+
+    contract.set_profile(profile("bio", ""));
+
+    let refund = transferred_to(&accounts(1));
+    assert!(refund > 0 && refund < MINT_DEPOSIT);
+}
+
+#[test]
+fn shrinking_profile_without_deposit() {
+    let mut contract = new_contract_with(&[], true, 0);
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.set_profile(profile("long bio", ""));
+
+    set_caller(accounts(1), 0);
+    contract.set_profile(profile("bio", ""));
+
+    assert_eq!(contract.get_profile(accounts(1)).unwrap().bio, "bio");
+}
@@ -0,0 +1,689 @@
+use near_sdk::test_utils::accounts;
+
+use super::*;
+
+fn mint_many(contract: &mut Contract, token_ids: &[&str], token_type: Option<&str>) {
+    for token_id in token_ids {
+        mint(contract, accounts(1), token_id, token_type);
+    }
+}
+
+#[test]
+fn nft_token_ids_for_type_partial_page() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    mint_many(&mut contract, &["1", "2", "3"], Some("A"));
+    mint_many(&mut contract, &["4"], Some("B"));
+
+    assert_eq!(
+        contract.nft_token_ids_for_type("A".to_string(), U64(0), U64(2)),
+        vec!["1".to_string(), "2".to_string()]
+    );
+}
+
+#[test]
+fn nft_token_ids_for_type_final_page() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint_many(&mut contract, &["1", "2", "3"], Some("A"));
+
+    assert_eq!(contract.nft_token_ids_for_type("A".to_string(), U64(2), U64(2)), vec!["3".to_string()]);
+    assert!(contract.nft_token_ids_for_type("A".to_string(), U64(3), U64(2)).is_empty());
+    assert!(contract.nft_token_ids_for_type("B".to_string(), U64(0), U64(2)).is_empty());
+}
+
+#[test]
+fn nft_tokens_for_owner_reversed_starts_with_last_minted() {
+    let mut contract = new_contract(&[]);
+    mint_many(&mut contract, &["1", "2", "3"], None);
+
+    let token_ids: Vec<TokenId> = contract
+        .nft_tokens_for_owner_reversed(accounts(1).into(), None, Some(2))
+        .into_iter()
+        .map(|token| token.token_id)
+        .collect();
+    assert_eq!(token_ids, vec!["3".to_string(), "2".to_string()]);
+}
+
+#[test]
+fn nft_tokens_for_owner_reversed_starts_with_last_received() {
+    let mut contract = new_contract(&[]);
+    mint_many(&mut contract, &["1", "2"], None);
+    mint(&mut contract, accounts(2), "3", None);
+    set_caller(accounts(2), 1);
+    contract.nft_transfer(accounts(1), "3".to_string(), None, None);
+
+    let tokens = contract.nft_tokens_for_owner_reversed(accounts(1).into(), None, Some(10));
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(tokens[0].token_id, "3");
+}
+
+#[test]
+fn storage_byte_cost_is_non_zero() {
+    let contract = new_contract(&[]);
+
+    assert!(contract.get_storage_byte_cost().0 > 0);
+    assert!(contract.get_extra_storage_in_bytes_per_token().0 > 0);
+}
+
+#[test]
+fn token_type_exists_for_known_types_only() {
+    let contract = new_contract(&[("A", 10)]);
+
+    assert!(contract.token_type_exists("A".to_string()));
+    assert!(!contract.token_type_exists("B".to_string()));
+}
+
+#[test]
+fn nft_tokens_with_royalty_for_lists_paying_tokens() {
+    let mut contract = new_contract(&[]);
+    let royalty: HashMap<AccountId, u32> = [(accounts(2).to_string(), 500)].iter().cloned().collect();
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), Some(royalty), None, None, None, None);
+    mint(&mut contract, accounts(1), "2", None);
+
+    let tokens = contract.nft_tokens_with_royalty_for(accounts(2).into(), U64(0), 10);
+
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0].token_id, "1");
+    assert!(contract.nft_tokens_with_royalty_for(accounts(3).into(), U64(0), 10).is_empty());
+}
+
+#[test]
+fn nft_token_exists_for_present_and_absent_ids() {
+    let mut contract = new_contract(&[]);
+    mint(&mut contract, accounts(1), "1", None);
+
+    assert!(contract.nft_token_exists("1".to_string()));
+    assert!(!contract.nft_token_exists("2".to_string()));
+}
+
+#[test]
+fn can_mint_when_all_checks_pass() {
+    let contract = new_contract(&[("A", 10)]);
+
+    assert!(contract.can_mint("A".to_string(), accounts(1).into()));
+}
+
+#[test]
+fn can_mint_fails_for_unknown_type() {
+    let contract = new_contract(&[("A", 10)]);
+
+    assert!(!contract.can_mint("B".to_string(), accounts(1).into()));
+}
+
+#[test]
+fn can_mint_fails_for_locked_type_except_owner() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    assert!(!contract.can_mint("A".to_string(), accounts(1).into()));
+    assert!(contract.can_mint("A".to_string(), owner().into()));
+}
+
+#[test]
+fn can_mint_fails_for_sold_out_type() {
+    let mut contract = new_contract(&[("A", 1)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+
+    assert!(!contract.can_mint("A".to_string(), accounts(1).into()));
+}
+
+#[test]
+fn can_mint_fails_at_global_supply_cap() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.set_global_supply_cap(Some(1));
+    mint(&mut contract, accounts(1), "1", None);
+
+    assert!(!contract.can_mint("A".to_string(), accounts(1).into()));
+}
+
+#[test]
+fn can_mint_fails_after_open_edition() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.set_open_edition("A".to_string(), U64(1_000));
+    testing_env!(get_context(accounts(1), 0).block_timestamp(1_000).build());
+
+    assert!(!contract.can_mint("A".to_string(), accounts(1).into()));
+}
+
+#[test]
+fn can_mint_fails_without_required_profile() {
+    let mut contract = new_contract(&[("A", 10)]);
+    set_caller(owner(), 0);
+    contract.set_require_profile_to_mint(true);
+
+    assert!(!contract.can_mint("A".to_string(), accounts(1).into()));
+}
+
+#[test]
+fn nft_supply_for_type_by_value() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint_many(&mut contract, &["1", "2"], Some("A"));
+    let token_type = "A".to_string();
+
+    assert_eq!(contract.nft_supply_for_type(token_type), U64(2));
+    assert_eq!(contract.nft_supply_for_type("B".to_string()), U64(0));
+}
+
+#[test]
+fn nft_tokens_for_creator_and_type_across_types() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    mint_many(&mut contract, &["1", "2"], Some("A"));
+    mint_many(&mut contract, &["3"], Some("B"));
+    mint_many(&mut contract, &["4"], None);
+    mint(&mut contract, accounts(2), "5", Some("A"));
+
+    let token_ids = |contract: &Contract, token_type: &str, from_index: u64| -> Vec<TokenId> {
+        contract
+            .nft_tokens_for_creator_and_type(accounts(1).into(), token_type.to_string(), U64(from_index), 10)
+            .into_iter()
+            .map(|token| token.token_id)
+            .collect()
+    };
+    assert_eq!(token_ids(&contract, "A", 0), vec!["1".to_string(), "2".to_string()]);
+    assert_eq!(token_ids(&contract, "A", 1), vec!["2".to_string()]);
+    assert_eq!(token_ids(&contract, "B", 0), vec!["3".to_string()]);
+    assert!(token_ids(&contract, "C", 0).is_empty());
+}
+
+#[test]
+fn nft_tokens_for_creator_and_type_skips_reminted_ids() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint_many(&mut contract, &["1", "2"], Some("A"));
+    set_caller(accounts(1), 1);
+    contract.nft_burn("1".to_string());
+    mint(&mut contract, accounts(2), "1", Some("A"));
+
+    let token_ids = |account_id: ValidAccountId| -> Vec<TokenId> {
+        contract
+            .nft_tokens_for_creator_and_type(account_id.into(), "A".to_string(), U64(0), 10)
+            .into_iter()
+            .map(|token| token.token_id)
+            .collect()
+    };
+    assert_eq!(token_ids(accounts(1)), vec!["2".to_string()]);
+    assert_eq!(token_ids(accounts(2)), vec!["1".to_string()]);
+}
+
+#[test]
+fn nft_tokens_for_owner_with_locks_flags_locked_types() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    mint_many(&mut contract, &["1"], Some("A"));
+    mint_many(&mut contract, &["2"], Some("B"));
+    mint_many(&mut contract, &["3"], None);
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    let locks: Vec<(TokenId, bool)> = contract
+        .nft_tokens_for_owner_with_locks(accounts(1).into(), None, Some(10))
+        .into_iter()
+        .map(|(token, locked)| (token.token_id, locked))
+        .collect();
+    assert_eq!(locks, vec![("1".to_string(), true), ("2".to_string(), false), ("3".to_string(), false)]);
+}
+
+#[test]
+fn nft_token_royalty_for_present_and_missing_token() {
+    let mut contract = new_contract(&[]);
+    let royalty: HashMap<AccountId, u32> = [(accounts(2).to_string(), 500)].iter().cloned().collect();
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), Some(royalty.clone()), None, None, None, None);
+
+    assert_eq!(contract.nft_token_royalty("1".to_string()), Some(royalty));
+    assert_eq!(contract.nft_token_royalty("2".to_string()), None);
+}
+
+#[test]
+fn nft_token_creator_for_present_and_absent_tokens() {
+    let mut contract = new_contract(&[]);
+    set_caller(accounts(1), MINT_DEPOSIT);
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), None, Some(accounts(2)), None, None, None);
+
+    assert_eq!(contract.nft_token_creator("1".to_string()), contract.nft_token("1".to_string()).unwrap().creator_id);
+    // the receiver of a mint is recorded as its creator, like in tokens_per_creator
+    assert_eq!(contract.nft_token_creator("1".to_string()), Some(accounts(2).into()));
+    assert_eq!(contract.nft_token_creator("2".to_string()), None);
+}
+
+#[test]
+fn backfill_token_creators_fills_missing_creators() {
+    let mut contract = new_contract(&[]);
+    mint_many(&mut contract, &["1", "2"], None);
+    contract.creator_by_id.remove(&"1".to_string());
+    contract.creator_by_id.remove(&"2".to_string());
+    assert_eq!(contract.nft_token_creator("1".to_string()), None);
+
+    set_caller(owner(), 0);
+    contract.backfill_token_creators(accounts(1).into(), U64(0), 10);
+
+    assert_eq!(contract.nft_token_creator("1".to_string()), Some(accounts(1).into()));
+    assert_eq!(contract.nft_token_creator("2".to_string()), Some(accounts(1).into()));
+}
+
+#[test]
+fn enumerations_past_the_end_are_empty() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint_many(&mut contract, &["1", "2"], Some("A"));
+    let account_id: AccountId = accounts(1).into();
+
+    assert!(contract.nft_tokens(Some(U128(5)), Some(10)).is_empty());
+    assert!(contract.nft_tokens_paged(Some(U128(5)), Some(10)).0.is_empty());
+    assert!(contract.nft_tokens_from_end(Some(U128(5)), Some(10)).is_empty());
+    assert!(contract.nft_tokens_for_type("A".to_string(), U64(5), 10).is_empty());
+    assert!(contract.nft_token_ids_for_type("A".to_string(), U64(5), U64(10)).is_empty());
+    assert!(contract.nft_tokens_for_owner(account_id.clone(), Some(U128(5)), Some(10)).is_empty());
+    assert!(contract.nft_tokens_for_owner_reversed(account_id.clone(), Some(U128(5)), Some(10)).is_empty());
+    assert!(contract.nft_tokens_for_creator(account_id.clone(), U64(5), 10).is_empty());
+    assert!(contract.nft_token_ids_for_creator(account_id.clone(), U64(5), 10).is_empty());
+    assert!(contract.nft_tokens_for_creator_and_type(account_id.clone(), "A".to_string(), U64(5), 10).is_empty());
+    assert!(contract.nft_tokens_for_creator_locked(account_id.clone(), U64(5), 10).is_empty());
+    assert!(contract.nft_untyped_tokens(U64(5), 10).is_empty());
+    assert!(contract.nft_tokens_with_royalty_for(account_id.clone(), U64(5), 10).is_empty());
+    assert!(contract.nft_search_by_title("1".to_string(), U64(5), 10).is_empty());
+    assert!(contract.owner_type_breakdown(account_id, U64(5), 10).is_empty());
+    assert!(contract.get_profile_accounts(U64(5), 10).is_empty());
+}
+
+#[test]
+fn validate_mint_reports_first_failing_check() {
+    let mut contract = new_contract(&[("A", 10), ("B", 1), ("C", 10)]);
+    mint_many(&mut contract, &["1"], Some("B"));
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["C".to_string()]);
+    let account_id: AccountId = accounts(1).into();
+    let validate = |token_type: &str, metadata: TokenMetadata| {
+        contract.validate_mint(token_type.to_string(), metadata, account_id.clone())
+    };
+    let mut long_title = sample_token_metadata("title");
+    long_title.title = Some("t".repeat(MAX_TOKEN_TITLE_LENGTH));
+
+    assert_eq!(validate("A", sample_token_metadata("title")), Ok(()));
+    assert_eq!(validate("D", sample_token_metadata("title")), Err("Token type must have supply cap.".to_string()));
+    assert_eq!(validate("C", sample_token_metadata("title")), Err("Only the owner can mint tokens of a locked type".to_string()));
+    assert_eq!(validate("B", sample_token_metadata("title")), Err("Cannot mint anymore of token type.".to_string()));
+    assert_eq!(validate("A", long_title), Err("Token title length is too long".to_string()));
+}
+
+#[test]
+fn account_storage_usage_grows_with_minting() {
+    let mut contract = new_contract(&[]);
+    assert_eq!(contract.get_account_storage_usage(accounts(1).into()), U64(0));
+
+    mint_many(&mut contract, &["1"], None);
+    let after_one = contract.get_account_storage_usage(accounts(1).into()).0;
+    mint_many(&mut contract, &["2"], None);
+    let after_two = contract.get_account_storage_usage(accounts(1).into()).0;
+
+    assert!(after_one > contract.get_extra_storage_in_bytes_per_token().0);
+    assert_eq!(after_two, 2 * after_one);
+}
+
+#[test]
+fn nft_supply_for_types_total_sums_types() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    mint_many(&mut contract, &["1", "2"], Some("A"));
+    mint_many(&mut contract, &["3"], Some("B"));
+
+    let total = contract.nft_supply_for_types_total(vec!["A".to_string(), "B".to_string(), "C".to_string()]);
+
+    assert_eq!(
+        total.0,
+        contract.nft_supply_for_type("A".to_string()).0 + contract.nft_supply_for_type("B".to_string()).0
+    );
+    assert_eq!(total, U64(3));
+}
+
+#[test]
+fn nft_tokens_limit_clamped_to_max_page_size() {
+    let mut contract = new_contract(&[]);
+    for i in 0..=MAX_PAGE_SIZE {
+        mint(&mut contract, accounts(1), &i.to_string(), None);
+    }
+
+    assert_eq!(contract.nft_tokens(None, Some(MAX_PAGE_SIZE + 50)).len(), MAX_PAGE_SIZE as usize);
+}
+
+#[test]
+fn type_admin_view_matches_getters() {
+    let mut contract = new_contract(&[("A", 10), ("B", 5)]);
+    mint_many(&mut contract, &["1"], Some("A"));
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["B".to_string()]);
+    contract.set_unique_media_for_type("A".to_string(), true);
+    contract.set_free_mints_for_type("B".to_string(), Some(2));
+    contract.set_open_edition("A".to_string(), U64(1_000));
+
+    let types = contract.get_type_admin_view();
+
+    assert_eq!(types.len(), 2);
+    for info in types {
+        let token_type = info.token_type.clone();
+        assert_eq!(Some(&info.supply_cap), contract.get_supply_caps().get(&token_type));
+        assert_eq!(info.minted, contract.nft_supply_for_type(token_type.clone()));
+        assert_eq!(info.locked, contract.get_token_types_locked().contains(&token_type));
+        assert_eq!(info.unique_media, contract.get_unique_media_types().contains(&token_type));
+        assert_eq!(info.free_mints, contract.free_mints_by_type.get(&token_type));
+        assert_eq!(info.open_edition_until, contract.get_open_edition_until(token_type.clone()));
+    }
+}
+
+#[test]
+fn hidden_token_left_out_of_enumerations() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint_many(&mut contract, &["1", "2"], Some("A"));
+    set_caller(owner(), 0);
+    contract.hide_token("1".to_string());
+    let account_id: AccountId = accounts(1).into();
+    let ids = |tokens: Vec<JsonToken>| -> Vec<TokenId> { tokens.into_iter().map(|token| token.token_id).collect() };
+    let visible = vec!["2".to_string()];
+
+    assert_eq!(ids(contract.nft_tokens(None, Some(10))), visible);
+    assert_eq!(ids(contract.nft_tokens_paged(None, Some(10)).0), visible);
+    assert_eq!(ids(contract.nft_tokens_from_end(None, Some(10))), visible);
+    assert_eq!(ids(contract.nft_latest_tokens(10)), visible);
+    assert_eq!(ids(contract.nft_tokens_for_type("A".to_string(), U64(0), 10)), visible);
+    assert_eq!(contract.nft_token_ids_for_type("A".to_string(), U64(0), U64(10)), vec!["1", "2"]);
+    assert_eq!(ids(contract.nft_tokens_for_owner(account_id.clone(), None, Some(10))), visible);
+    assert_eq!(ids(contract.nft_tokens_for_owner_reversed(account_id.clone(), None, Some(10))), visible);
+    assert_eq!(ids(contract.nft_tokens_for_creator(account_id.clone(), U64(0), 10)), visible);
+    assert_eq!(ids(contract.nft_tokens_for_creator_and_type(account_id, "A".to_string(), U64(0), 10)), visible);
+    assert!(contract.nft_token("1".to_string()).is_some());
+
+    contract.unhide_token("1".to_string());
+    assert_eq!(contract.nft_tokens(None, Some(10)).len(), 2);
+}
+
+#[test]
+fn hidden_tokens_keep_raw_page_offsets() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint_many(&mut contract, &["1", "2", "3", "4", "5"], Some("A"));
+    set_caller(owner(), 0);
+    contract.hide_token("2".to_string());
+    let account_id: AccountId = accounts(1).into();
+    let ids = |tokens: Vec<JsonToken>| -> Vec<TokenId> { tokens.into_iter().map(|token| token.token_id).collect() };
+    let expected = vec![vec!["1".to_string()], vec!["3".to_string(), "4".to_string()], vec!["5".to_string()]];
+
+    // every page starts at from_index + limit, whatever was hidden on the previous one
+    let pages = |page: &dyn Fn(u64) -> Vec<JsonToken>| -> Vec<Vec<TokenId>> { (0..3).map(|i| ids(page(i * 2))).collect() };
+    assert_eq!(pages(&|from_index| contract.nft_tokens(Some(U128(from_index.into())), Some(2))), expected);
+    assert_eq!(pages(&|from_index| contract.nft_tokens_for_owner(account_id.clone(), Some(U128(from_index.into())), Some(2))), expected);
+    assert_eq!(pages(&|from_index| contract.nft_tokens_for_type("A".to_string(), U64(from_index), 2)), expected);
+    assert_eq!(pages(&|from_index| contract.nft_tokens_for_creator(account_id.clone(), U64(from_index), 2)), expected);
+    assert_eq!(
+        pages(&|from_index| contract.nft_tokens_for_creator_and_type(account_id.clone(), "A".to_string(), U64(from_index), 2)),
+        expected
+    );
+    assert_eq!(
+        ids(contract.nft_tokens_for_owner_reversed(account_id, Some(U128(2)), Some(2))),
+        vec!["3".to_string()]
+    );
+}
+
+#[test]
+fn holder_distribution_buckets_holders() {
+    let mut contract = new_contract(&[]);
+    let holdings = [(accounts(1), 1), (accounts(2), 3), (accounts(3), 5), (accounts(4), 6)];
+    let mut next_token_id = 0;
+    for (account_id, held) in holdings.iter() {
+        for _ in 0..*held {
+            next_token_id += 1;
+            mint(&mut contract, account_id.clone(), &next_token_id.to_string(), None);
+        }
+    }
+
+    assert_eq!(contract.holder_distribution(U64(0), 10), vec![(1, 1), (2, 2), (6, 1)]);
+    assert_eq!(contract.holder_distribution(U64(0), 2), vec![(1, 1), (2, 1), (6, 0)]);
+}
+
+#[test]
+fn nft_type_sold_out_at_cap() {
+    let mut contract = new_contract(&[("A", 2)]);
+    mint_many(&mut contract, &["1"], Some("A"));
+    assert!(!contract.nft_type_sold_out("A".to_string()));
+
+    mint_many(&mut contract, &["2"], Some("A"));
+
+    assert!(contract.nft_type_sold_out("A".to_string()));
+    assert!(!contract.nft_type_sold_out("B".to_string()));
+}
+
+#[test]
+fn estimate_royalties_for_known_royalties() {
+    let mut contract = new_contract(&[]);
+    for (token_id, amount) in [("1", 500), ("2", 1000)].iter() {
+        let royalty: HashMap<AccountId, u32> = [(accounts(2).to_string(), *amount)].iter().cloned().collect();
+        set_caller(accounts(1), MINT_DEPOSIT);
+        contract.nft_mint(Some(token_id.to_string()), sample_token_metadata(token_id), Some(royalty), None, None, None, None);
+    }
+    mint_many(&mut contract, &["3"], None);
+
+    assert_eq!(contract.estimate_royalties_for(accounts(2).into(), U128(10_000), U64(0), 10), U128(1_500));
+    assert_eq!(contract.estimate_royalties_for(accounts(2).into(), U128(10_000), U64(1), 10), U128(1_000));
+    assert_eq!(contract.estimate_royalties_for(accounts(3).into(), U128(10_000), U64(0), 10), U128(0));
+}
+
+#[test]
+fn nft_untyped_tokens_lists_tokens_without_type() {
+    let mut contract = new_contract(&[("A", 10)]);
+    mint_many(&mut contract, &["1"], Some("A"));
+    mint_many(&mut contract, &["2", "3"], None);
+
+    let token_ids: Vec<TokenId> = contract
+        .nft_untyped_tokens(U64(0), 10)
+        .into_iter()
+        .map(|token| token.token_id)
+        .collect();
+    assert_eq!(token_ids, vec!["2".to_string(), "3".to_string()]);
+}
+
+#[test]
+fn current_timestamp_is_block_timestamp() {
+    let contract = new_contract(&[]);
+    testing_env!(get_context(accounts(1), 0).block_timestamp(1_234).build());
+
+    assert_eq!(contract.get_current_timestamp(), U64(1_234));
+}
+
+#[test]
+fn nft_latest_tokens_newest_first() {
+    let mut contract = new_contract(&[]);
+    mint_many(&mut contract, &["1", "2", "3"], None);
+
+    let token_ids: Vec<TokenId> = contract.nft_latest_tokens(2).into_iter().map(|token| token.token_id).collect();
+
+    assert_eq!(token_ids, vec!["3".to_string(), "2".to_string()]);
+    assert_eq!(contract.nft_latest_tokens(10).len(), 3);
+}
+
+#[test]
+fn nft_tokens_for_creator_locked_lists_locked_types_only() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    mint_many(&mut contract, &["1", "2", "5"], Some("A"));
+    mint_many(&mut contract, &["3"], Some("B"));
+    mint_many(&mut contract, &["4"], None);
+    // a burned id minted again by someone else is no longer the creator's
+    set_caller(accounts(1), 1);
+    contract.nft_burn("5".to_string());
+    mint(&mut contract, accounts(2), "5", Some("A"));
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    let token_ids = |account_id: ValidAccountId| -> Vec<TokenId> {
+        contract
+            .nft_tokens_for_creator_locked(account_id.into(), U64(0), 10)
+            .into_iter()
+            .map(|token| token.token_id)
+            .collect()
+    };
+
+    assert_eq!(token_ids(accounts(1)), vec!["1".to_string(), "2".to_string()]);
+    assert_eq!(token_ids(accounts(2)), vec!["5".to_string()]);
+}
+
+#[test]
+fn owner_type_breakdown_for_mixed_holder() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10)]);
+    mint_many(&mut contract, &["1", "2"], Some("B"));
+    mint_many(&mut contract, &["3"], Some("A"));
+    mint_many(&mut contract, &["4"], None);
+
+    assert_eq!(
+        contract.owner_type_breakdown(accounts(1).into(), U64(0), 10),
+        vec![("A".to_string(), U64(1)), ("B".to_string(), U64(2))]
+    );
+}
+
+#[test]
+fn nft_search_by_title_matches_prefix_case_insensitively() {
+    let mut contract = new_contract(&[]);
+    for (token_id, title) in [("1", "Sunset Beach"), ("2", "sunrise"), ("3", "Moon")].iter() {
+        set_caller(accounts(1), MINT_DEPOSIT);
+        contract.nft_mint(Some(token_id.to_string()), sample_token_metadata(title), None, None, None, None, None);
+    }
+
+    let token_ids: Vec<TokenId> = contract
+        .nft_search_by_title("SUN".to_string(), U64(0), 10)
+        .into_iter()
+        .map(|token| token.token_id)
+        .collect();
+
+    assert_eq!(token_ids, vec!["1".to_string(), "2".to_string()]);
+    assert!(contract.nft_search_by_title("star".to_string(), U64(0), 10).is_empty());
+}
+
+fn mintable_types_at(contract: &Contract, block_timestamp: u64) -> Vec<String> {
+    testing_env!(get_context(accounts(1), 0).block_timestamp(block_timestamp).build());
+    let mut token_types = contract.currently_mintable_types();
+    token_types.sort();
+    token_types
+}
+
+#[test]
+fn currently_mintable_types_follow_mint_checks() {
+    let mut contract = new_contract(&[("A", 10), ("B", 10), ("C", 1), ("D", 10)]);
+    set_caller(owner(), 0);
+    contract.set_open_edition("A".to_string(), U64(1_000));
+    contract.lock_token_types(vec!["B".to_string()]);
+    mint_many(&mut contract, &["1"], Some("C"));
+
+    assert_eq!(mintable_types_at(&contract, 0), vec!["A".to_string(), "D".to_string()]);
+    assert_eq!(mintable_types_at(&contract, 999), vec!["A".to_string(), "D".to_string()]);
+    assert_eq!(mintable_types_at(&contract, 1_000), vec!["D".to_string()]);
+}
+
+#[test]
+fn nft_tokens_paged_walks_every_page() {
+    let mut contract = new_contract(&[]);
+    mint_many(&mut contract, &["1", "2", "3", "4", "5"], None);
+    set_caller(owner(), 0);
+    contract.hide_token("2".to_string());
+
+    let mut token_ids = vec![];
+    let mut pages = 0;
+    let mut from_index = None;
+    loop {
+        let (tokens, next_index) = contract.nft_tokens_paged(from_index, Some(2));
+        pages += 1;
+        token_ids.extend(tokens.into_iter().map(|token| token.token_id));
+        if next_index.is_none() {
+            break;
+        }
+        from_index = next_index;
+    }
+
+    assert_eq!(token_ids, vec!["1", "3", "4", "5"]);
+    assert_eq!(pages, 2);
+}
+
+#[test]
+fn account_summary_matches_supply_views() {
+    let mut contract = new_contract(&[]);
+    mint_many(&mut contract, &["1", "2", "3"], None);
+    set_caller(accounts(1), 1);
+    contract.nft_transfer(accounts(2), "1".to_string(), None, None);
+
+    for account_id in [accounts(1), accounts(2), accounts(3)].iter() {
+        let account_id: AccountId = account_id.clone().into();
+        let (created, owned) = contract.account_summary(account_id.clone());
+        assert_eq!(u128::from(created.0), u128::from(contract.nft_supply_for_creator(account_id.clone())));
+        assert_eq!(owned, contract.nft_supply_for_owner(account_id));
+    }
+    assert_eq!(contract.account_summary(accounts(1).into()), (U64(3), U128(2)));
+    assert_eq!(contract.account_summary(accounts(2).into()), (U64(0), U128(1)));
+}
+
+#[test]
+fn nft_token_ids_for_creator_matches_tokens_for_creator() {
+    let mut contract = new_contract(&[]);
+    mint_many(&mut contract, &["1", "2", "3"], None);
+
+    let token_ids: Vec<TokenId> = contract
+        .nft_tokens_for_creator(accounts(1).into(), U64(0), 10)
+        .into_iter()
+        .map(|token| token.token_id)
+        .collect();
+    assert_eq!(contract.nft_token_ids_for_creator(accounts(1).into(), U64(0), 10), token_ids);
+    assert_eq!(contract.nft_token_ids_for_creator(accounts(1).into(), U64(1), 1), vec!["2"]);
+}
+
+#[test]
+fn nft_token_ids_for_creator_keeps_burned_ids() {
+    let mut contract = new_contract(&[]);
+    mint_many(&mut contract, &["1", "2"], None);
+    set_caller(accounts(1), 1);
+    contract.nft_burn("1".to_string());
+
+    assert_eq!(contract.nft_token_ids_for_creator(accounts(1).into(), U64(0), 10), vec!["1", "2"]);
+    assert_eq!(contract.nft_tokens_for_creator(accounts(1).into(), U64(0), 10).len(), 1);
+}
+
+#[test]
+fn nft_token_full_matches_dedicated_views() {
+    let mut contract = new_contract(&[("A", 5)]);
+    set_caller(accounts(1), MINT_DEPOSIT);
+    let royalty: HashMap<AccountId, u32> = [(accounts(2).to_string(), 500)].iter().cloned().collect();
+    contract.nft_mint(Some("1".to_string()), sample_token_metadata("1"), Some(royalty), None, Some("A".to_string()), None, None);
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    let full = contract.nft_token_full("1".to_string()).unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&full.token).unwrap(),
+        serde_json::to_string(&contract.nft_token("1".to_string()).unwrap()).unwrap()
+    );
+    assert_eq!(full.locked, contract.is_token_locked("1".to_string()));
+    assert_eq!(full.remaining_supply, contract.get_remaining_supply("A".to_string()));
+    assert_eq!(full.last_transfer_at, contract.nft_token_last_transfer("1".to_string()));
+    assert_eq!(full.token.creator_id, contract.nft_token_creator("1".to_string()));
+    assert_eq!(Some(full.token.royalty), contract.nft_token_royalty("1".to_string()));
+    assert!(contract.nft_token_full("2".to_string()).is_none());
+}
+
+#[test]
+fn nft_supported_standards_lists_implemented_standards() {
+    let contract = new_contract(&[]);
+
+    let standards = contract.nft_supported_standards();
+
+    for standard in ["nep171", "nep177", "nep178", "nep181"].iter() {
+        assert!(standards.contains(&(standard.to_string(), "1.0.0".to_string())), "{} missing", standard);
+    }
+    assert_eq!(standards.len(), 4);
+}
+
+#[test]
+fn nft_tokens_locked_status_in_given_order() {
+    let mut contract = new_contract(&[("A", 5), ("B", 5)]);
+    mint(&mut contract, accounts(1), "1", Some("A"));
+    mint(&mut contract, accounts(1), "2", Some("B"));
+    mint(&mut contract, accounts(1), "3", None);
+    set_caller(owner(), 0);
+    contract.lock_token_types(vec!["A".to_string()]);
+
+    let token_ids = ["2", "1", "3", "4"].iter().map(|token_id| token_id.to_string()).collect();
+
+    assert_eq!(contract.nft_tokens_locked_status(token_ids), vec![false, true, false, false]);
+}
@@ -3,7 +3,8 @@ use crate::*;
 pub type TokenId = String;
 pub type Payout = HashMap<AccountId, U128>;
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
 pub struct Token {
     pub owner_id: AccountId,
     pub approved_account_ids: HashMap<AccountId, U64>,
@@ -25,4 +26,18 @@ pub struct JsonToken {
     // CUSTOM - fields
     pub royalty: HashMap<AccountId, u32>,
     pub token_type: Option<String>,
+    pub transferable: bool,
+    /// None for tokens minted before creators were recorded and not backfilled yet
+    pub creator_id: Option<AccountId>,
+    pub approvals_expire_at: HashMap<AccountId, U64>,
+}
+
+/// CUSTOM - everything a token page shows, creator and royalty are part of token
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenFull {
+    pub token: JsonToken,
+    pub locked: bool,
+    pub remaining_supply: Option<U64>,
+    pub last_transfer_at: Option<U64>,
 }
@@ -42,7 +42,7 @@ export const getFileData = async (hash) => {
   let response;
 
   try {
-    response = await fetch(`${pinataHref}/${hash}`);
+    response = await fetch(`${pinataHref}/${hash.replace(/^ipfs:\/\//, '')}`);
   } catch (e) {
     console.error(e);
 
@@ -152,12 +152,11 @@ const MintReview = ({ backLink, nft }) => {
         }}
         bidAvailable={false}
       />
-      {!isFreeMintAvailable && (
-        <p className="fee-description">
-          We will ask to attach {formatNearAmount(APP.DEPOSIT_DEFAULT)} NEAR to mint transaction to cover storage fees.
-          All unused funds will be returned to your account in the same transaction.
-        </p>
-      )}
+      <p className="fee-description">
+        We will ask to attach {formatNearAmount(APP.DEPOSIT_DEFAULT)} NEAR to mint transaction to cover storage fees.
+        {isFreeMintAvailable && ' Your mint is free, only the small cost of indexing your NFT is kept.'} All unused
+        funds will be returned to your account in the same transaction.
+      </p>
       <StickedToBottom isSecondary>
         <StyledButton isSecondary isDisabled={isMinting}>
           <Link to={backLink}>Replace Art</Link>
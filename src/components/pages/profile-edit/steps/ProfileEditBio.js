@@ -18,6 +18,8 @@ import { useIsUnmounting } from '~/hooks';
 
 import { getFileData } from '~/apis';
 
+import { toIpfsUri } from '~/utils/strings';
+
 import { PROFILE, QUERY_KEYS } from '~/constants';
 
 const Container = styled('div')`
@@ -148,7 +150,7 @@ function ProfileEditBio({ uploadPhotoLink }) {
       await setProfile({
         ...profile,
         bio: bioEdited,
-        image: profile?.image || '',
+        image: toIpfsUri(profile?.image) || '',
       });
     } catch (error) {
       console.error(error);
@@ -18,6 +18,8 @@ import { uploadFile } from '~/apis';
 
 import { PROFILE, QUERY_KEYS } from '~/constants';
 
+import { toIpfsUri } from '~/utils/strings';
+
 const Container = styled('div')`
   .heading-small {
     margin-bottom: 10px;
@@ -107,7 +109,7 @@ function ProfileEditPhoto() {
     try {
       await setProfile({
         ...profile,
-        image: fileHash,
+        image: toIpfsUri(fileHash),
         bio: profile?.bio || '',
       });
     } catch (error) {
@@ -8,7 +8,6 @@ import { GOT_MIN_STORAGE } from './types';
 
 import { ReactChildrenTypeRequired } from '~types/ReactChildrenTypes';
 
-import { NearContext } from '../near';
 import { NftContractContext } from '~/contexts';
 
 import { getMarketContractName } from '~/utils';
@@ -19,8 +18,7 @@ export const MarketContractContext = React.createContext(initialMarketContractSt
 
 export const MarketContractContextProvider = ({ marketContract, children }) => {
   const [marketContractState, dispatchMarketContract] = useReducer(marketContractReducer, initialMarketContractState);
-  const { user } = useContext(NearContext);
-  const { nftContract, getGemsBatch, getGem, getIsFreeMintAvailable } = useContext(NftContractContext);
+  const { nftContract, getGemsBatch, getGem } = useContext(NftContractContext);
 
   const getSale = useCallback(
     async (gemId) => {
@@ -74,8 +72,6 @@ export const MarketContractContextProvider = ({ marketContract, children }) => {
 
   const mintAndListGem = useCallback(
     async (nft) => {
-      const isFreeMintAvailable = await getIsFreeMintAvailable(user.accountId);
-
       const metadata = {
         media: nft.media,
         reference: APP.HASH_SOURCE,
@@ -112,7 +108,7 @@ export const MarketContractContextProvider = ({ marketContract, children }) => {
             })
           ),
           APP.PREPAID_GAS_LIMIT / 2,
-          APP.USE_STORAGE_FEES || !isFreeMintAvailable ? APP.DEPOSIT_DEFAULT : 0
+          APP.DEPOSIT_DEFAULT
         ),
         transactions.functionCall(
           'nft_approve',
@@ -112,7 +112,8 @@ export const NftContractContextProvider = ({ nftContract, children }) => {
               profile,
             })
           ),
-          APP.PREPAID_GAS_LIMIT_HALF
+          APP.PREPAID_GAS_LIMIT_HALF,
+          APP.USE_STORAGE_FEES ? APP.DEPOSIT_DEFAULT : 0
         ),
       ]);
     },
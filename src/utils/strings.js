@@ -1 +1,3 @@
 export const capitalizeFirstLetter = (string) => `${string.charAt(0).toUpperCase()}${string.slice(1)}`;
+
+export const toIpfsUri = (hash) => (hash && !hash.includes('://') ? `ipfs://${hash}` : hash);